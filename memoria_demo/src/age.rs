@@ -0,0 +1,95 @@
+//! Cálculo de idade: aproximada (só o ano) ou exata (data completa).
+
+use chrono::{Datelike, NaiveDate};
+
+/// ano de nascimento mais antigo aceito
+pub const MIN_BIRTH_YEAR: i32 = 1900;
+
+/// Rejeita anos no futuro ou anteriores a [`MIN_BIRTH_YEAR`].
+pub fn validate_birth_year(y: i32, current: i32) -> Result<i32, String> {
+    if y > current {
+        Err(format!("{} está no futuro (ano atual: {})", y, current))
+    } else if y < MIN_BIRTH_YEAR {
+        Err(format!("{} é anterior a {}", y, MIN_BIRTH_YEAR))
+    } else {
+        Ok(y)
+    }
+}
+
+/// Primeira sequência de exatamente 4 dígitos no texto, ignorando o resto:
+/// `" 2001 "`, `"ano: 1998"` e `"nasci em 2001"` dão o ano; `"abcd"` e
+/// `"12345"` dão `None`. A faixa aceita fica com [`validate_birth_year`].
+pub fn parse_year(input: &str) -> Option<i32> {
+    input
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|run| run.len() == 4)
+        .and_then(|run| run.parse().ok())
+}
+
+/// `DDDD-DD-DD`, mesmo que a data não exista
+fn looks_like_date(input: &str) -> bool {
+    let bytes = input.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+/// Nascimento informado pelo usuário.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Birth {
+    /// apenas `YYYY`: idade aproximada
+    Year(i32),
+    /// `YYYY-MM-DD`: idade em anos completos
+    Date(NaiveDate),
+}
+
+impl Birth {
+    /// Aceita `YYYY-MM-DD` ou um ano solto no texto (ver [`parse_year`]).
+    /// Algo com cara de data mas inválido (`2001-02-30`) é rejeitado em vez
+    /// de virar só o ano.
+    pub fn parse(input: &str) -> Option<Birth> {
+        let input = input.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+            return Some(Birth::Date(date));
+        }
+        if looks_like_date(input) {
+            return None;
+        }
+        parse_year(input).map(Birth::Year)
+    }
+
+    pub fn year(self) -> i32 {
+        match self {
+            Birth::Year(year) => year,
+            Birth::Date(date) => date.year(),
+        }
+    }
+
+    /// Idade em `today`: diferença de anos, ou anos completos se a data é conhecida.
+    pub fn age_on(self, today: NaiveDate) -> i32 {
+        match self {
+            Birth::Year(year) => today.year() - year,
+            Birth::Date(date) => completed_years(date, today),
+        }
+    }
+}
+
+/// Anos completos entre `birth` e `today`.
+pub fn completed_years(birth: NaiveDate, today: NaiveDate) -> i32 {
+    let years = today.year() - birth.year();
+    if today < birthday_in(birth, today.year()) {
+        years - 1
+    } else {
+        years
+    }
+}
+
+/// Aniversário de `birth` no ano `year`; quem nasceu em 29/02 faz
+/// aniversário em 28/02 nos anos não bissextos.
+pub fn birthday_in(birth: NaiveDate, year: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, birth.month(), birth.day())
+        .or_else(|| NaiveDate::from_ymd_opt(year, 2, 28))
+        .expect("28/02 existe em todo ano")
+}
@@ -0,0 +1,90 @@
+//! Alocador global que conta bytes alocados/liberados no heap (`--alloc-stats`).
+//!
+//! O alocador sempre envolve o `System`, mas só atualiza os contadores
+//! depois de [`enable`]; sem a flag o custo é uma leitura atômica por chamada.
+//!
+//! A biblioteca não instala o alocador: quem quiser os contadores declara
+//! `#[global_allocator] static GLOBAL: CountingAllocator = CountingAllocator;`
+//! no próprio binário (como `main.rs`). Sem isso, [`snapshot`] fica em zero.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Wrapper sobre [`System`] que soma os tamanhos pedidos e devolvidos.
+pub struct CountingAllocator;
+
+fn count(counter: &AtomicUsize, bytes: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        counter.fetch_add(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(&ALLOCATED, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(&ALLOCATED, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count(&DEALLOCATED, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // realloc = liberar o bloco antigo + alocar o novo
+            count(&DEALLOCATED, layout.size());
+            count(&ALLOCATED, new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Fotografia dos contadores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// total de bytes alocados desde [`enable`]
+    pub allocated: usize,
+    /// total de bytes liberados desde [`enable`]
+    pub deallocated: usize,
+}
+
+impl AllocStats {
+    /// bytes ainda vivos; satura em zero porque blocos alocados antes de
+    /// [`enable`] podem ser liberados depois
+    pub fn live(&self) -> usize {
+        self.allocated.saturating_sub(self.deallocated)
+    }
+}
+
+/// Liga a contagem (idempotente).
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocated: ALLOCATED.load(Ordering::Relaxed),
+        deallocated: DEALLOCATED.load(Ordering::Relaxed),
+    }
+}
@@ -0,0 +1,57 @@
+//! Mapa de memória em ASCII (flag `--ascii-map`), para terminais sem Graphviz.
+//!
+//! Os endereços reais ficam a terabytes de distância uns dos outros; em vez
+//! de uma escala linear, cada item ganha uma linha (ordem por posto) e o
+//! espaço entre dois vizinhos vira de 0 a [`MAX_GAP_ROWS`] linhas `:`,
+//! proporcionais ao log2 da distância.
+
+use crate::render::{format_address, section};
+use crate::AddressReport;
+use std::io::{self, Write};
+
+/// maior número de linhas de separação entre dois itens
+pub const MAX_GAP_ROWS: u32 = 4;
+
+/// bits de distância por linha de separação (2^10 = 1 KiB, 2^20 = 1 MiB...)
+const BITS_PER_ROW: u32 = 10;
+
+/// linhas `:` que representam a distância entre dois itens vizinhos
+pub fn gap_rows(gap: usize) -> u32 {
+    match gap.checked_ilog2() {
+        Some(bits) => (bits / BITS_PER_ROW).min(MAX_GAP_ROWS),
+        None => 0,
+    }
+}
+
+/// Desenha o mapa: endereços altos (stack) no topo, baixos (heap, rodata,
+/// text) embaixo.
+pub fn write_ascii_map(out: &mut dyn Write, report: &AddressReport) -> io::Result<()> {
+    let anchors = report.anchors();
+    let mut items = report.labeled();
+    items.sort_by_key(|&(_, addr)| std::cmp::Reverse(addr));
+
+    section(out, "Mapa de memória (ASCII)")?;
+    writeln!(out, "+-- endereços altos")?;
+    let mut previous: Option<usize> = None;
+    for (label, addr) in items {
+        if let Some(prev) = previous {
+            let gap = prev - addr;
+            for row in 0..gap_rows(gap) {
+                if row == 0 {
+                    writeln!(out, ":   ~2^{} bytes", gap.ilog2())?;
+                } else {
+                    writeln!(out, ":")?;
+                }
+            }
+        }
+        writeln!(
+            out,
+            "| {:<18} {:<7} {}",
+            label,
+            anchors.classify(addr).label(),
+            format_address(addr)
+        )?;
+        previous = Some(addr);
+    }
+    writeln!(out, "+-- endereços baixos")
+}
@@ -0,0 +1,58 @@
+//! Subcomando `bench`: quanto custa alocar na stack vs no heap.
+
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// tamanho do array / `Vec` alocado em cada iteração
+pub const BUFFER_SIZE: usize = 1024;
+/// iterações por medição
+pub const ITERATIONS: u32 = 100_000;
+
+/// nanossegundos por iteração
+fn per_op(elapsed: Duration, iterations: u32) -> f64 {
+    elapsed.as_nanos() as f64 / iterations as f64
+}
+
+/// Cria `[u8; BUFFER_SIZE]` na stack e `vec![0u8; BUFFER_SIZE]` no heap
+/// `iterations` vezes cada, passando o resultado por `black_box` para o
+/// otimizador não apagar o trabalho.
+pub fn run_bench(out: &mut dyn Write, iterations: u32) -> io::Result<()> {
+    writeln!(
+        out,
+        "--- bench: {} bytes, {} iterações ---",
+        BUFFER_SIZE, iterations
+    )?;
+
+    let start = Instant::now();
+    for i in 0..iterations {
+        let mut array = [0u8; BUFFER_SIZE];
+        array[0] = i as u8;
+        black_box(&array);
+    }
+    let stack = per_op(start.elapsed(), iterations);
+
+    let start = Instant::now();
+    for i in 0..iterations {
+        let mut vec = vec![0u8; BUFFER_SIZE];
+        vec[0] = i as u8;
+        black_box(&vec);
+    }
+    let heap = per_op(start.elapsed(), iterations);
+
+    writeln!(out, "stack [u8; {}] : {:>8.1} ns/op", BUFFER_SIZE, stack)?;
+    writeln!(out, "heap  Vec<u8>    : {:>8.1} ns/op", heap)?;
+    if heap > stack {
+        writeln!(
+            out,
+            "=> o heap custou ~{:.1}x a stack: alocar na stack é só mover o ponteiro da pilha (e zerar os bytes); \
+             no heap há a chamada ao alocador e a liberação no drop.",
+            heap / stack.max(0.1)
+        )
+    } else {
+        writeln!(
+            out,
+            "=> desta vez o heap não pareceu mais caro (medição ruidosa?); rode em --release e repita."
+        )
+    }
+}
@@ -0,0 +1,12 @@
+//! Checksum FNV-1a (64 bits) sobre bytes quaisquer, sem dependências.
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash FNV-1a: para cada byte, `xor` e depois multiplica pelo primo.
+/// Não é criptográfico — serve para detectar se algum byte mudou.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(FNV_PRIME)
+    })
+}
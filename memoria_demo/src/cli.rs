@@ -0,0 +1,393 @@
+//! Parsing dos argumentos de linha de comando (sem dependências externas).
+
+use crate::age::{self, Birth};
+use crate::color::ColorChoice;
+use crate::demos::{
+    CODE_ADDRESS_CHILD_FLAG, DEFAULT_RECURSION_DEPTH, MAX_RECURSION_DEPTH, OVERFLOW_CHILD_FLAG,
+};
+use crate::exit::Exit;
+use crate::i18n::Lang;
+use crate::FIELDS;
+use chrono::{Datelike, Local};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// limite de `--repeat` (cada coleta imprime uma linha)
+pub const MAX_REPEAT: usize = 1000;
+
+/// padrão de `--max-name-len`, em caracteres
+pub const DEFAULT_MAX_NAME_LEN: usize = 256;
+
+/// Subcomandos (primeiro argumento que não começa com `--`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subcommand {
+    /// `aslr`: só a demo de ASLR, que lança o binário duas vezes e compara
+    Aslr,
+    /// `bench`: tempo de alocação na stack vs no heap
+    Bench,
+    /// `diff A.json B.json`: compara dois relatórios salvos com `--json`
+    Diff,
+    /// `compare-launches`: roda o binário duas vezes e compara os relatórios
+    CompareLaunches,
+}
+
+impl Subcommand {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "aslr" => Some(Subcommand::Aslr),
+            "bench" => Some(Subcommand::Bench),
+            "diff" => Some(Subcommand::Diff),
+            "compare-launches" => Some(Subcommand::CompareLaunches),
+            _ => None,
+        }
+    }
+}
+
+/// Opções reconhecidas na linha de comando.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// `--name NOME`: pula o prompt do nome
+    pub name: Option<String>,
+    /// `--birth-year YYYY` (ou `YYYY-MM-DD`): pula o prompt do nascimento
+    pub birth: Option<Birth>,
+    /// `--max-name-len N`: nomes mais longos são truncados em N caracteres
+    pub max_name_len: usize,
+    // `--input FILE` não tem campo próprio: o arquivo é lido durante o
+    // parsing e preenche `name` e `birth`
+    /// `--record FILE`: grava cada resposta digitada em `FILE`, uma por linha
+    pub record: Option<String>,
+    /// `--replay FILE`: lê as respostas de uma gravação em vez do stdin
+    pub replay: Option<String>,
+    /// `--json`: emite o relatório em JSON no stdout (prompts vão para o stderr)
+    pub json: bool,
+    /// `--dot`: emite o mapa de memória em Graphviz DOT no stdout
+    pub dot: bool,
+    /// `--csv`: emite os endereços em CSV no stdout
+    pub csv: bool,
+    /// `--quiet`: só nome, nascimento e idade (ignorado com `--json`/`--dot`/`--csv`)
+    pub quiet: bool,
+    /// `--self-check`: confere as relações de memória e sai (código 6 se falhar)
+    pub self_check: bool,
+    /// `--timestamp`: data e hora (ISO 8601) no início da saída ou, com
+    /// `--json`, no campo `timestamp`
+    pub timestamp: bool,
+    /// `--ascii-map`: desenha um mapa de memória em ASCII após o relatório
+    pub ascii_map: bool,
+    /// `--threads`: inclui a demonstração de `Arc` entre threads
+    pub threads: bool,
+    /// `--lang pt|en`: idioma da saída (padrão `pt`)
+    pub lang: Lang,
+    /// `--color always|never|auto`: cores ANSI por região (padrão `auto`)
+    pub color: ColorChoice,
+    /// `--alloc-stats`: conta os bytes alocados no heap e imprime no fim
+    pub alloc_stats: bool,
+    /// `--no-addresses`: nomes simbólicos (`<stack:name>`) no lugar de endereços
+    pub no_addresses: bool,
+    /// `--relative`: endereços como deslocamentos a partir do menor capturado
+    pub relative: bool,
+    /// `--width 8|16|auto`: dígitos hex por endereço (`None` = `auto`)
+    pub width: Option<usize>,
+    /// `--repeat N`: repete a coleta de endereços N vezes no mesmo processo
+    pub repeat: usize,
+    /// `--depth N`: profundidade da demonstração de recursão
+    pub depth: usize,
+    /// `--only a,b`: só estas demos (nomes de `MemoryDemo::name`)
+    pub only: Vec<String>,
+    /// `--skip x,y`: todas as demos menos estas
+    pub skip: Vec<String>,
+    /// `--fields a,b`: só estes endereços no relatório, JSON e CSV
+    pub fields: Vec<String>,
+    /// `--fail-fast`: não captura o panic de uma demo (aborta como antes)
+    pub fail_fast: bool,
+    /// `--demo-overflow`: libera o estouro de pilha controlado no menu
+    pub demo_overflow: bool,
+    /// argumento interno: este processo é o filho que vai estourar a pilha
+    pub overflow_child: bool,
+    /// argumento interno: este processo é o filho que imprime o endereço do código
+    pub code_address_child: bool,
+    /// subcomando, se houver (ex.: `aslr`)
+    pub subcommand: Option<Subcommand>,
+    /// argumentos posicionais do subcomando (os dois arquivos de `diff`)
+    pub operands: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            name: None,
+            birth: None,
+            max_name_len: DEFAULT_MAX_NAME_LEN,
+            record: None,
+            replay: None,
+            json: false,
+            dot: false,
+            csv: false,
+            quiet: false,
+            self_check: false,
+            timestamp: false,
+            ascii_map: false,
+            threads: false,
+            lang: Lang::default(),
+            color: ColorChoice::default(),
+            alloc_stats: false,
+            no_addresses: false,
+            relative: false,
+            width: None,
+            repeat: 1,
+            depth: DEFAULT_RECURSION_DEPTH,
+            only: Vec::new(),
+            skip: Vec::new(),
+            fields: Vec::new(),
+            fail_fast: false,
+            demo_overflow: false,
+            overflow_child: false,
+            code_address_child: false,
+            subcommand: None,
+            operands: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    /// Saída para máquinas (JSON, DOT, CSV): o stdout fica só com o documento e
+    /// os prompts vão para o stderr.
+    pub fn machine_output(&self) -> bool {
+        self.json || self.dot || self.csv
+    }
+
+    /// Prompts e avisos vão para o stderr: o stdout fica só com o documento
+    /// (saída para máquinas) ou só com o resultado (`--quiet`).
+    pub fn prompts_to_stderr(&self) -> bool {
+        self.machine_output() || self.quiet
+    }
+}
+
+/// nascimento vindo de fora do prompt (flag ou arquivo), com a mesma
+/// validação do caminho interativo
+fn parse_birth(v: &str) -> Result<Birth, String> {
+    let birth = Birth::parse(v).ok_or_else(|| format!("'{}'", v.trim()))?;
+    age::validate_birth_year(birth.year(), Local::now().year())?;
+    Ok(birth)
+}
+
+/// `a,b, c` -> `["a", "b", "c"]` (itens vazios são ignorados)
+fn name_list(v: &str) -> Vec<String> {
+    v.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Lê o cenário de `--input`: nome na primeira linha, nascimento na segunda.
+pub fn read_input_file(path: &Path) -> Result<(String, Birth), String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut lines = text.lines();
+    let (name, birth) = match (lines.next(), lines.next()) {
+        (Some(name), Some(birth)) => (name.trim(), birth),
+        _ => return Err("esperadas duas linhas (nome e ano de nascimento)".to_string()),
+    };
+    if name.is_empty() {
+        return Err("nome vazio na primeira linha".to_string());
+    }
+    let birth = parse_birth(birth).map_err(|e| format!("ano de nascimento inválido: {}", e))?;
+    Ok((name.to_string(), birth))
+}
+
+/// variável de ambiente que pré-preenche o nome
+pub const NAME_VAR: &str = "MEMORIA_NAME";
+/// variável de ambiente que pré-preenche o nascimento (`YYYY` ou `YYYY-MM-DD`)
+pub const BIRTH_YEAR_VAR: &str = "MEMORIA_BIRTH_YEAR";
+
+/// Completa com [`NAME_VAR`] e [`BIRTH_YEAR_VAR`] o que as flags não deram
+/// (flags e `--input` têm prioridade; sem nada, o prompt continua).
+///
+/// `var` é a consulta ao ambiente (`std::env::var(..).ok()` no binário).
+pub fn apply_env<F>(opts: &mut Options, var: F) -> Result<(), CliError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if opts.name.is_none() {
+        opts.name = var(NAME_VAR).filter(|name| !name.trim().is_empty());
+    }
+    if opts.birth.is_none() {
+        if let Some(v) = var(BIRTH_YEAR_VAR) {
+            let birth =
+                parse_birth(&v).map_err(|e| format!("{} inválido: {}", BIRTH_YEAR_VAR, e))?;
+            opts.birth = Some(birth);
+        }
+    }
+    Ok(())
+}
+
+/// Erro de [`parse_args`]; cada variante tem o seu código de saída.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// flag desconhecida ou valor inválido
+    Usage(String),
+    /// problema no arquivo de `--input`
+    InputFile(String),
+}
+
+impl CliError {
+    pub fn exit(&self) -> Exit {
+        match self {
+            CliError::Usage(_) => Exit::BadArgs,
+            CliError::InputFile(_) => Exit::BadInputFile,
+        }
+    }
+}
+
+impl From<String> for CliError {
+    fn from(msg: String) -> Self {
+        CliError::Usage(msg)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(msg) | CliError::InputFile(msg) => f.write_str(msg),
+        }
+    }
+}
+
+/// Interpreta os argumentos (sem o nome do programa).
+///
+/// Aceita `--flag valor` e `--flag=valor`; devolve a mensagem de erro para
+/// ser exibida no stderr quando algo não é reconhecido.
+pub fn parse_args<I>(args: I) -> Result<Options, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut opts = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg, None),
+        };
+        let mut value = |flag: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} requer um valor", flag))
+        };
+        match flag.as_str() {
+            "--name" => opts.name = Some(value("--name")?),
+            "--birth-year" => {
+                let v = value("--birth-year")?;
+                let birth = parse_birth(&v).map_err(|e| format!("--birth-year inválido: {}", e))?;
+                opts.birth = Some(birth);
+            }
+            "--input" => {
+                let path = value("--input")?;
+                let (name, birth) = read_input_file(Path::new(&path))
+                    .map_err(|e| CliError::InputFile(format!("--input '{}': {}", path, e)))?;
+                opts.name = Some(name);
+                opts.birth = Some(birth);
+            }
+            "--record" => opts.record = Some(value("--record")?),
+            "--replay" => opts.replay = Some(value("--replay")?),
+            "--json" => opts.json = true,
+            "--dot" => opts.dot = true,
+            "--csv" => opts.csv = true,
+            "--quiet" => opts.quiet = true,
+            "--ascii-map" => opts.ascii_map = true,
+            "--timestamp" => opts.timestamp = true,
+            "--self-check" => opts.self_check = true,
+            "--threads" => opts.threads = true,
+            "--relative" => opts.relative = true,
+            "--no-addresses" => opts.no_addresses = true,
+            "--alloc-stats" => opts.alloc_stats = true,
+            "--demo-overflow" => opts.demo_overflow = true,
+            "--fail-fast" => opts.fail_fast = true,
+            "--only" => opts.only = name_list(&value("--only")?),
+            "--skip" => opts.skip = name_list(&value("--skip")?),
+            "--fields" => {
+                let fields = name_list(&value("--fields")?);
+                if let Some(bad) = fields.iter().find(|f| !FIELDS.contains(&f.as_str())) {
+                    return Err(format!(
+                        "--fields: campo desconhecido '{}' (válidos: {})",
+                        bad,
+                        FIELDS.join(", ")
+                    )
+                    .into());
+                }
+                opts.fields = fields;
+            }
+            OVERFLOW_CHILD_FLAG => opts.overflow_child = true,
+            CODE_ADDRESS_CHILD_FLAG => opts.code_address_child = true,
+            "--color" => {
+                let v = value("--color")?;
+                opts.color = ColorChoice::from_code(&v)
+                    .ok_or_else(|| format!("--color inválido: '{}'", v))?;
+            }
+            "--lang" => {
+                let v = value("--lang")?;
+                opts.lang =
+                    Lang::from_code(&v).ok_or_else(|| format!("--lang inválido: '{}'", v))?;
+            }
+            "--width" => {
+                let v = value("--width")?;
+                opts.width = match v.trim() {
+                    "auto" => None,
+                    "8" => Some(8),
+                    "16" => Some(16),
+                    _ => {
+                        return Err(format!("--width inválido: '{}' (use 8, 16 ou auto)", v).into())
+                    }
+                };
+            }
+            "--repeat" => {
+                let v = value("--repeat")?;
+                opts.repeat = match v.trim().parse() {
+                    Ok(n) if (1..=MAX_REPEAT).contains(&n) => n,
+                    _ => {
+                        return Err(
+                            format!("--repeat inválido: '{}' (use 1..={})", v, MAX_REPEAT).into(),
+                        )
+                    }
+                };
+            }
+            "--max-name-len" => {
+                let v = value("--max-name-len")?;
+                opts.max_name_len = match v.trim().parse() {
+                    Ok(n) if n >= 1 => n,
+                    _ => {
+                        return Err(
+                            format!("--max-name-len inválido: '{}' (use 1 ou mais)", v).into()
+                        )
+                    }
+                };
+            }
+            "--depth" => {
+                let v = value("--depth")?;
+                opts.depth = match v.trim().parse() {
+                    Ok(n) if n <= MAX_RECURSION_DEPTH => n,
+                    _ => {
+                        return Err(format!(
+                            "--depth inválido: '{}' (use 0..={})",
+                            v, MAX_RECURSION_DEPTH
+                        )
+                        .into())
+                    }
+                };
+            }
+            other if opts.subcommand == Some(Subcommand::Diff) && !other.starts_with("--") => {
+                opts.operands.push(other.to_string())
+            }
+            other => match Subcommand::from_name(other) {
+                Some(sub) if opts.subcommand.is_none() => opts.subcommand = Some(sub),
+                _ => return Err(format!("argumento desconhecido: '{}'", other).into()),
+            },
+        }
+    }
+    if opts.subcommand == Some(Subcommand::Diff) && opts.operands.len() != 2 {
+        return Err("diff requer dois arquivos JSON (ex.: diff a.json b.json)"
+            .to_string()
+            .into());
+    }
+    Ok(opts)
+}
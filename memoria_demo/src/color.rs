@@ -0,0 +1,72 @@
+//! Cores ANSI por região de memória (flag `--color`).
+//!
+//! Sem dependências: só alguns códigos de escape. O estado é global, como em
+//! [`crate::alloc_stats`], para não precisar passar mais um parâmetro por
+//! todas as funções de impressão.
+
+use crate::region::MemoryRegion;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Valor de `--color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    Always,
+    Never,
+    /// colore apenas se o stdout for um terminal
+    #[default]
+    Auto,
+}
+
+impl ColorChoice {
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            "auto" => Some(ColorChoice::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// Liga ou desliga as cores conforme a escolha (`Auto` consulta o stdout).
+pub fn init(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => io::stdout().is_terminal(),
+    };
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Envolve `text` na cor da região: verde para stack, vermelho para heap,
+/// azul para rodata/text. Com as cores desligadas devolve o texto intacto.
+pub fn paint(region: MemoryRegion, text: &str) -> String {
+    let code = match region {
+        MemoryRegion::Stack => "32",
+        MemoryRegion::Heap => "31",
+        MemoryRegion::Rodata | MemoryRegion::Text => "34",
+        MemoryRegion::Unknown => return text.to_string(),
+    };
+    if is_enabled() {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Negrito, usado nos cabeçalhos de seção; com as cores desligadas devolve
+/// o texto intacto.
+pub fn bold(text: &str) -> String {
+    if is_enabled() {
+        format!("\x1b[1m{}\x1b[0m", text)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,71 @@
+//! Exportação dos endereços em CSV (flag `--csv`).
+
+use crate::{render, AddressReport, WELCOME};
+use std::io::{self, Write};
+
+/// cabeçalho, sempre emitido (mesmo que nenhuma linha siga)
+pub const HEADER: &str = "label,region,address_hex,size_bytes";
+
+/// Escreve uma linha por item capturado (só os de `--fields`, se houver),
+/// com a região inferida pelas âncoras do próprio relatório.
+///
+/// Os endereços saem com zeros à esquerda até a largura de `--width` (por
+/// padrão a do ponteiro: 16 dígitos em 64 bits), para que as colunas
+/// ordenem como texto.
+/// O tamanho de `example_function` fica vazio: o tamanho de uma função
+/// não é observável em Rust.
+pub fn write_report_csv(out: &mut dyn Write, report: &AddressReport) -> io::Result<()> {
+    let rows = [
+        ("welcome", report.welcome, Some(WELCOME.len())),
+        ("name", report.name, Some(report.name_layout.size)),
+        (
+            "name_buffer",
+            report.name_buffer,
+            Some(report.name_buffer_layout.size),
+        ),
+        (
+            "stack_value",
+            report.stack_value,
+            Some(report.stack_value_layout.size),
+        ),
+        (
+            "heap_box",
+            report.heap_box,
+            Some(report.heap_box_layout.size),
+        ),
+        (
+            "heap_box_pointee",
+            report.heap_box_pointee,
+            Some(report.heap_box_pointee_layout.size),
+        ),
+        (
+            "name_chars",
+            report.name_chars,
+            Some(report.name_chars_layout.size),
+        ),
+        (
+            "name_chars_buffer",
+            report.name_chars_buffer,
+            Some(report.name_chars_buffer_layout.size),
+        ),
+        ("example_function", report.example_function, None),
+    ];
+    let anchors = report.anchors();
+
+    writeln!(out, "{}", HEADER)?;
+    for (label, addr, size) in rows {
+        if !render::field_shown(label) {
+            continue;
+        }
+        let size = size.map(|s| s.to_string()).unwrap_or_default();
+        writeln!(
+            out,
+            "{},{},{},{}",
+            label,
+            anchors.classify(addr),
+            render::hex_address(addr),
+            size
+        )?;
+    }
+    Ok(())
+}
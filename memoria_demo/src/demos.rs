@@ -0,0 +1,3014 @@
+//! Demonstrações independentes de conceitos de memória.
+//!
+//! Cada `demo_*` escreve sua própria seção no `Write` recebido; o binário
+//! decide quais rodar a partir de [`registry`].
+
+use crate::alloc_stats;
+use crate::checksum::fnv1a;
+use crate::hexdump::hexdump;
+use crate::i18n::{msg, Lang, Msg};
+use crate::render::{hex_address, pointer_address, section};
+use crate::tracer::{self, Tracer};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::marker::PhantomPinned;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::process::{self, Command};
+use std::ptr;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// Interface comum das demonstrações: um nome estável e a execução em um `Write`.
+pub trait MemoryDemo {
+    fn name(&self) -> &str;
+    fn run(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Dados do estudante (e opções) disponíveis para as demonstrações.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemoContext {
+    pub name: String,
+    pub age: i32,
+    /// profundidade do `demo_recursion` (`--depth`)
+    pub recursion_depth: usize,
+    /// inclui as demos que criam threads (`--threads`)
+    pub threads: bool,
+    /// inclui o estouro de pilha controlado (`--demo-overflow`)
+    pub overflow: bool,
+    /// itens do relatório classificados como stack (rótulo, endereço)
+    pub stack_items: Vec<(&'static str, usize)>,
+}
+
+impl DemoContext {
+    /// contexto com as opções nos valores padrão
+    pub fn new(name: &str, age: i32) -> Self {
+        DemoContext {
+            name: name.to_string(),
+            age,
+            recursion_depth: DEFAULT_RECURSION_DEPTH,
+            threads: false,
+            overflow: false,
+            stack_items: Vec::new(),
+        }
+    }
+}
+
+/// profundidade padrão do `demo_recursion`
+pub const DEFAULT_RECURSION_DEPTH: usize = 20;
+/// limite da flag `--depth`, bem abaixo do que estouraria a stack principal
+pub const MAX_RECURSION_DEPTH: usize = 1000;
+
+/// Declara uma demo sem estado (struct de tamanho zero) que delega para `$run(out)`.
+macro_rules! simple_demo {
+    ($ty:ident, $name:literal, $run:ident) => {
+        #[doc = concat!("Demo `", $name, "` (ver [`", stringify!($run), "`]).")]
+        pub struct $ty;
+
+        impl MemoryDemo for $ty {
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+                $run(out)
+            }
+        }
+    };
+}
+
+simple_demo!(RcDemo, "rc", demo_rc);
+simple_demo!(DropOrderDemo, "drop_order", demo_drop_order);
+simple_demo!(ReallocDemo, "realloc", demo_realloc);
+simple_demo!(
+    LiteralInterningDemo,
+    "literal_interning",
+    demo_literal_interning
+);
+simple_demo!(NicheDemo, "niche", demo_niche);
+simple_demo!(MoveDemo, "move", demo_move);
+simple_demo!(SwapDemo, "swap", demo_swap);
+
+/// Demo `capacity` (ver [`demo_capacity`]).
+pub struct CapacityDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for CapacityDemo {
+    fn name(&self) -> &str {
+        "capacity"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_capacity(out, &self.name)
+    }
+}
+
+/// Demo `str_kinds` (ver [`demo_str_kinds`]).
+pub struct StrKindsDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for StrKindsDemo {
+    fn name(&self) -> &str {
+        "str_kinds"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_str_kinds(out, &self.name)
+    }
+}
+
+/// Demo `fat_pointers` (ver [`demo_fat_pointers`]).
+pub struct FatPointersDemo {
+    pub age: i32,
+}
+
+impl MemoryDemo for FatPointersDemo {
+    fn name(&self) -> &str {
+        "fat_pointers"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_fat_pointers(out, self.age)
+    }
+}
+
+/// Demo `recursion` (ver [`demo_recursion`]).
+pub struct RecursionDemo {
+    pub depth: usize,
+}
+
+impl MemoryDemo for RecursionDemo {
+    fn name(&self) -> &str {
+        "recursion"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_recursion(out, self.depth)
+    }
+}
+
+simple_demo!(OverflowDemo, "overflow", demo_overflow);
+simple_demo!(InteriorMutDemo, "interior_mut", demo_interior_mut);
+simple_demo!(WeakDemo, "weak", demo_weak);
+simple_demo!(LayoutDemo, "layout", demo_layout);
+simple_demo!(ZstDemo, "zst", demo_zst);
+simple_demo!(ClosuresDemo, "closures", demo_closures);
+simple_demo!(RawBoxDemo, "raw_box", demo_raw_box);
+simple_demo!(LeakDemo, "leak", demo_leak);
+simple_demo!(
+    CapacityStrategiesDemo,
+    "capacity_strategies",
+    demo_capacity_strategies
+);
+simple_demo!(EnumLayoutDemo, "enum_layout", demo_enum_layout);
+simple_demo!(PinDemo, "pin", demo_pin);
+simple_demo!(HashMapDemo, "hashmap", demo_hashmap);
+simple_demo!(StaticConstDemo, "static_const", demo_static_const);
+simple_demo!(NestedBoxDemo, "nested_box", demo_nested_box);
+simple_demo!(BufferReuseDemo, "buffer_reuse", demo_buffer_reuse);
+simple_demo!(PtrArithmeticDemo, "ptr_arithmetic", demo_ptr_arithmetic);
+simple_demo!(VtableDemo, "vtable", demo_vtable);
+simple_demo!(BoxedSliceDemo, "boxed_slice", demo_boxed_slice);
+simple_demo!(CloneCostDemo, "clone_cost", demo_clone_cost);
+simple_demo!(AlignmentDemo, "alignment", demo_alignment);
+simple_demo!(FrameGapDemo, "frame_gap", demo_frame_gap);
+simple_demo!(NestedDropDemo, "nested_drop", demo_nested_drop);
+simple_demo!(DrainDemo, "drain", demo_drain);
+simple_demo!(StringGrowthDemo, "string_growth", demo_string_growth);
+simple_demo!(LargeArrayDemo, "large_array", demo_large_array);
+simple_demo!(BoxIntoVecDemo, "box_into_vec", demo_box_into_vec);
+simple_demo!(StableRefsDemo, "stable_refs", demo_stable_refs);
+simple_demo!(UnboxDemo, "unbox", demo_unbox);
+simple_demo!(IoBufferingDemo, "io_buffering", demo_io_buffering);
+simple_demo!(BoxDropTimingDemo, "box_drop_timing", demo_box_drop_timing);
+simple_demo!(ProvenanceDemo, "provenance", demo_provenance);
+simple_demo!(VecInternalsDemo, "vec_internals", demo_vec_internals);
+simple_demo!(OptionRefDemo, "option_ref", demo_option_ref);
+simple_demo!(TupleVsStructDemo, "tuple_vs_struct", demo_tuple_vs_struct);
+
+/// Demo `handle` (ver [`demo_handle`]).
+pub struct HandleDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for HandleDemo {
+    fn name(&self) -> &str {
+        "handle"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_handle(out, &self.name)
+    }
+}
+
+/// Demo `cow` (ver [`demo_cow`]).
+pub struct CowDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for CowDemo {
+    fn name(&self) -> &str {
+        "cow"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_cow(out, &self.name)
+    }
+}
+
+/// Demo `hexdump` (ver [`demo_hexdump`]).
+pub struct HexdumpDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for HexdumpDemo {
+    fn name(&self) -> &str {
+        "hexdump"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_hexdump(out, &self.name)
+    }
+}
+
+/// Demo `checksum` (ver [`demo_checksum`]).
+pub struct ChecksumDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for ChecksumDemo {
+    fn name(&self) -> &str {
+        "checksum"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_checksum(out, &self.name)
+    }
+}
+
+/// Demo `slices` (ver [`demo_slices`]).
+pub struct SlicesDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for SlicesDemo {
+    fn name(&self) -> &str {
+        "slices"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_slices(out, &self.name)
+    }
+}
+
+/// Demo `utf8` (ver [`demo_utf8`]).
+pub struct Utf8Demo {
+    pub name: String,
+}
+
+impl MemoryDemo for Utf8Demo {
+    fn name(&self) -> &str {
+        "utf8"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_utf8(out, &self.name)
+    }
+}
+
+/// Demo `stack_base` (ver [`demo_stack_base`]).
+pub struct StackBaseDemo {
+    pub items: Vec<(&'static str, usize)>,
+}
+
+impl MemoryDemo for StackBaseDemo {
+    fn name(&self) -> &str {
+        "stack_base"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_stack_base(out, &self.items)
+    }
+}
+
+/// Demo `as_str` (ver [`demo_as_str`]).
+pub struct AsStrDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for AsStrDemo {
+    fn name(&self) -> &str {
+        "as_str"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_as_str(out, &self.name)
+    }
+}
+
+/// Demo `capacity_on_move` (ver [`demo_capacity_on_move`]).
+pub struct CapacityOnMoveDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for CapacityOnMoveDemo {
+    fn name(&self) -> &str {
+        "capacity_on_move"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_capacity_on_move(out, &self.name)
+    }
+}
+
+/// Demo `arc` (ver [`demo_arc`]).
+pub struct ArcDemo {
+    pub name: String,
+}
+
+impl MemoryDemo for ArcDemo {
+    fn name(&self) -> &str {
+        "arc"
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        demo_arc(out, &self.name)
+    }
+}
+
+/// Todas as demonstrações, na ordem do menu; novas demos entram só aqui.
+///
+/// As que criam threads ou derrubam um processo só entram quando liberadas
+/// no contexto (`--threads`, `--demo-overflow`).
+pub fn registry(cx: &DemoContext) -> Vec<Box<dyn MemoryDemo>> {
+    let mut demos: Vec<Box<dyn MemoryDemo>> = vec![
+        Box::new(HandleDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(CapacityDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(RcDemo),
+        Box::new(DropOrderDemo),
+        Box::new(ReallocDemo),
+        Box::new(LiteralInterningDemo),
+        Box::new(StrKindsDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(FatPointersDemo { age: cx.age }),
+        Box::new(NicheDemo),
+        Box::new(MoveDemo),
+        Box::new(SwapDemo),
+        Box::new(RecursionDemo {
+            depth: cx.recursion_depth,
+        }),
+        Box::new(InteriorMutDemo),
+        Box::new(WeakDemo),
+        Box::new(CowDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(HexdumpDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(LayoutDemo),
+        Box::new(ZstDemo),
+        Box::new(ClosuresDemo),
+        Box::new(RawBoxDemo),
+        Box::new(LeakDemo),
+        Box::new(CapacityStrategiesDemo),
+        Box::new(EnumLayoutDemo),
+        Box::new(PinDemo),
+        Box::new(HashMapDemo),
+        Box::new(StaticConstDemo),
+        Box::new(NestedBoxDemo),
+        Box::new(BufferReuseDemo),
+        Box::new(ChecksumDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(SlicesDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(PtrArithmeticDemo),
+        Box::new(VtableDemo),
+        Box::new(Utf8Demo {
+            name: cx.name.clone(),
+        }),
+        Box::new(BoxedSliceDemo),
+        Box::new(CloneCostDemo),
+        Box::new(AlignmentDemo),
+        Box::new(FrameGapDemo),
+        Box::new(NestedDropDemo),
+        Box::new(DrainDemo),
+        Box::new(StringGrowthDemo),
+        Box::new(LargeArrayDemo),
+        Box::new(AsStrDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(BoxIntoVecDemo),
+        Box::new(StackBaseDemo {
+            items: cx.stack_items.clone(),
+        }),
+        Box::new(StableRefsDemo),
+        Box::new(UnboxDemo),
+        Box::new(IoBufferingDemo),
+        Box::new(BoxDropTimingDemo),
+        Box::new(ProvenanceDemo),
+        Box::new(VecInternalsDemo),
+        Box::new(CapacityOnMoveDemo {
+            name: cx.name.clone(),
+        }),
+        Box::new(OptionRefDemo),
+        Box::new(TupleVsStructDemo),
+    ];
+    if cx.threads {
+        demos.push(Box::new(ArcDemo {
+            name: cx.name.clone(),
+        }));
+    }
+    if cx.overflow {
+        demos.push(Box::new(OverflowDemo));
+    }
+    demos
+}
+
+/// Aplica `--only` e `--skip` ao registro, mantendo a ordem do menu.
+///
+/// `only` vazio significa "todas". Um nome que não existe no registro é um
+/// erro, com a lista de nomes válidos.
+pub fn select(
+    registry: Vec<Box<dyn MemoryDemo>>,
+    only: &[String],
+    skip: &[String],
+) -> Result<Vec<Box<dyn MemoryDemo>>, String> {
+    if let Some(unknown) = only
+        .iter()
+        .chain(skip)
+        .find(|name| !registry.iter().any(|demo| demo.name() == name.as_str()))
+    {
+        let valid: Vec<&str> = registry.iter().map(|demo| demo.name()).collect();
+        return Err(format!(
+            "demo desconhecida: '{}' (válidas: {})",
+            unknown,
+            valid.join(", ")
+        ));
+    }
+    Ok(registry
+        .into_iter()
+        .filter(|demo| only.is_empty() || only.iter().any(|n| n == demo.name()))
+        .filter(|demo| !skip.iter().any(|n| n == demo.name()))
+        .collect())
+}
+
+/// Roda as demos em ordem e devolve os nomes das que entraram em pânico.
+///
+/// Um panic numa demo vira "demo falhou: X" (no idioma de `lang`) e a
+/// próxima segue normalmente;
+/// com `fail_fast` (`--fail-fast`) o panic não é capturado.
+pub fn run_demos(
+    out: &mut dyn Write,
+    demos: &[&dyn MemoryDemo],
+    fail_fast: bool,
+    lang: Lang,
+) -> io::Result<Vec<String>> {
+    let mut failed = Vec::new();
+    for demo in demos {
+        if fail_fast {
+            demo.run(out)?;
+            continue;
+        }
+        // `out` pode ficar com uma linha pela metade; é só texto didático
+        match panic::catch_unwind(AssertUnwindSafe(|| demo.run(out))) {
+            Ok(result) => result?,
+            Err(_) => {
+                writeln!(out, "\n{} {}", msg(Msg::DemoFailed, lang), demo.name())?;
+                failed.push(demo.name().to_string());
+            }
+        }
+    }
+    Ok(failed)
+}
+
+/// `Rc`: várias "donas" para a mesma alocação no heap, com contagem de referências.
+pub fn demo_rc(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Rc: posse compartilhada")?;
+    let original = Rc::new(String::from("compartilhado"));
+    writeln!(
+        out,
+        "original: strong_count = {}, pointee = {}",
+        Rc::strong_count(&original),
+        pointer_address(Rc::as_ptr(&original))
+    )?;
+    {
+        // clonar um Rc só incrementa o contador; o String no heap não é copiado
+        let a = Rc::clone(&original);
+        writeln!(
+            out,
+            "clone a : strong_count = {}, pointee = {}",
+            Rc::strong_count(&original),
+            pointer_address(Rc::as_ptr(&a))
+        )?;
+        let b = Rc::clone(&original);
+        writeln!(
+            out,
+            "clone b : strong_count = {}, pointee = {}",
+            Rc::strong_count(&original),
+            pointer_address(Rc::as_ptr(&b))
+        )?;
+        // a e b saem de escopo aqui
+    }
+    writeln!(
+        out,
+        "após drop de a e b: strong_count = {}",
+        Rc::strong_count(&original)
+    )?;
+    writeln!(
+        out,
+        "(um Box seria movido — só um dono; um Rc é clonado — todos apontam para o mesmo heap)"
+    )
+}
+
+/// `Arc`: o mesmo `String` compartilhado entre threads, sem cópia por thread.
+pub fn demo_arc(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Arc: posse compartilhada entre threads")?;
+    let shared = Arc::new(name.to_string());
+    writeln!(
+        out,
+        "main  : pointee = {}",
+        pointer_address(Arc::as_ptr(&shared))
+    )?;
+    let handles: Vec<_> = (0..3)
+        .map(|i| {
+            let shared = Arc::clone(&shared);
+            // cada thread devolve sua linha; só a thread principal escreve em `out`
+            thread::spawn(move || {
+                // cada thread tem sua própria stack: o local muda, o pointee não
+                let local = i;
+                format!(
+                    "thread {}: pointee = {}, local (stack da thread) = {}",
+                    i,
+                    pointer_address(Arc::as_ptr(&shared)),
+                    pointer_address(&local)
+                )
+            })
+        })
+        .collect();
+    for handle in handles {
+        let line = handle.join().expect("thread do demo_arc falhou");
+        writeln!(out, "{}", line)?;
+    }
+    writeln!(
+        out,
+        "(pointee idêntico em todas as threads; as stacks de cada thread são distintas)"
+    )
+}
+
+/// Locais são destruídos na ordem inversa da declaração (LIFO); o `Box`
+/// libera sua alocação no heap no momento em que ele próprio é destruído.
+pub fn demo_drop_order(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Ordem de drop (LIFO)")?;
+    let mut boxed_at = 0;
+    // os drops acontecem todos no fim do escopo; capturamos e escrevemos depois
+    let drops = tracer::capture_drops(|| {
+        let _a = Tracer("a (stack)");
+        let _b = Tracer("b (stack)");
+        let boxed = Box::new(Tracer("c (Box, heap)"));
+        boxed_at = &*boxed as *const Tracer as usize;
+        let _d = Tracer("d (stack)");
+    });
+    writeln!(out, "c vive no heap em {}", hex_address(boxed_at))?;
+    writeln!(out, "fim do escopo: drops em ordem inversa de declaração")?;
+    for name in drops {
+        writeln!(out, "drop: {}", name)?;
+    }
+    Ok(())
+}
+
+/// `String`/`Vec` reservam mais do que usam: `len` é o que está ocupado,
+/// `capacity` é o que já foi alocado no heap.
+pub fn demo_capacity(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Capacidade vs comprimento")?;
+    let name = name.to_string();
+    let name_chars: Vec<char> = name.chars().collect();
+    writeln!(
+        out,
+        "name       : len = {:>3}, capacity = {:>3}, buffer = {}",
+        name.len(),
+        name.capacity(),
+        pointer_address(name.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "name_chars : len = {:>3}, capacity = {:>3}, buffer = {}",
+        name_chars.len(),
+        name_chars.capacity(),
+        pointer_address(name_chars.as_ptr())
+    )?;
+
+    // o clone aloca exatamente o necessário; um push a mais força crescimento
+    let mut grown = name.clone();
+    writeln!(
+        out,
+        "clone      : len = {:>3}, capacity = {:>3}, buffer = {}",
+        grown.len(),
+        grown.capacity(),
+        pointer_address(grown.as_ptr())
+    )?;
+    let before = grown.as_ptr();
+    grown.push('!');
+    writeln!(
+        out,
+        "clone+'!'  : len = {:>3}, capacity = {:>3}, buffer = {}",
+        grown.len(),
+        grown.capacity(),
+        pointer_address(grown.as_ptr())
+    )?;
+    if before == grown.as_ptr() {
+        writeln!(out, "(o buffer cresceu no mesmo lugar)")
+    } else {
+        writeln!(
+            out,
+            "(o buffer foi realocado: capacidade cresceu e o endereço mudou)"
+        )
+    }
+}
+
+/// Um `Vec` cheio realoca ao receber mais um elemento: a capacidade dobra e o
+/// buffer pode mudar de endereço.
+pub fn demo_realloc(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Realocação do Vec")?;
+    let mut v: Vec<u32> = Vec::with_capacity(1);
+    writeln!(
+        out,
+        "inicial: len = {}, capacity = {}, buffer = {}",
+        v.len(),
+        v.capacity(),
+        pointer_address(v.as_ptr())
+    )?;
+    for i in 0..64 {
+        let (old_cap, old_ptr) = (v.capacity(), v.as_ptr());
+        v.push(i);
+        // a capacidade só muda quando len ultrapassou a capacidade anterior
+        if v.capacity() != old_cap {
+            let moved = if v.as_ptr() == old_ptr {
+                "no mesmo lugar"
+            } else {
+                "buffer moveu"
+            };
+            writeln!(
+                out,
+                "push #{:<2}: capacity {:>2} -> {:>3}, buffer {} -> {} ({})",
+                v.len(),
+                old_cap,
+                v.capacity(),
+                pointer_address(old_ptr),
+                pointer_address(v.as_ptr()),
+                moved
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Literais idênticos costumam ser "dobrados" em um único endereço no .rodata.
+pub fn demo_literal_interning(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Literais iguais no .rodata")?;
+    let first: &str = "memória";
+    let second: &str = "memória";
+    let other: &str = "outra coisa";
+    writeln!(
+        out,
+        "first  (\"memória\")     = {}",
+        pointer_address(first.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "second (\"memória\")     = {}",
+        pointer_address(second.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "other  (\"outra coisa\") = {}",
+        pointer_address(other.as_ptr())
+    )?;
+    let same = if first.as_ptr() == second.as_ptr() {
+        "sim"
+    } else {
+        "não"
+    };
+    writeln!(out, "first e second no mesmo endereço: {}", same)?;
+    writeln!(
+        out,
+        "(isso é uma otimização do compilador/linker, não uma garantia da linguagem)"
+    )
+}
+
+/// `&str`, `String` e `Box<str>`: três handles para bytes UTF-8.
+pub fn demo_str_kinds(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "&str vs String vs Box<str>")?;
+    let owned: String = name.to_string();
+    let slice: &str = owned.as_str();
+    let boxed: Box<str> = owned.clone().into_boxed_str();
+    writeln!(
+        out,
+        "{:<10} {:>18} {:>14} {:>12}",
+        "tipo", "buffer", "size_of handle", "len buffer"
+    )?;
+    writeln!(
+        out,
+        "{:<10} {:>18} {:>14} {:>12}",
+        "&str",
+        pointer_address(slice.as_ptr()),
+        mem::size_of::<&str>(),
+        slice.len()
+    )?;
+    writeln!(
+        out,
+        "{:<10} {:>18} {:>14} {:>12}",
+        "String",
+        pointer_address(owned.as_ptr()),
+        mem::size_of_val(&owned),
+        owned.len()
+    )?;
+    writeln!(
+        out,
+        "{:<10} {:>18} {:>14} {:>12}",
+        "Box<str>",
+        pointer_address(boxed.as_ptr()),
+        mem::size_of_val(&boxed),
+        boxed.len()
+    )?;
+    writeln!(
+        out,
+        "(&str e Box<str> são ptr + len; String tem também capacity, por isso é maior)"
+    )?;
+    writeln!(
+        out,
+        "(&str aponta para o buffer do String; Box<str> tem sua própria alocação)"
+    )
+}
+
+/// Ponteiros "gordos": slices e trait objects carregam uma segunda palavra.
+pub fn demo_fat_pointers(out: &mut dyn Write, age: i32) -> io::Result<()> {
+    section(out, "Ponteiros gordos (fat pointers)")?;
+    let word = mem::size_of::<usize>();
+    writeln!(out, "size_of::<usize>()            = {}", word)?;
+    writeln!(
+        out,
+        "size_of::<&i32>()             = {} (1 palavra: só o endereço)",
+        mem::size_of::<&i32>()
+    )?;
+    writeln!(
+        out,
+        "size_of::<&[i32]>()           = {} (2 palavras: endereço + len)",
+        mem::size_of::<&[i32]>()
+    )?;
+    writeln!(
+        out,
+        "size_of::<&dyn Display>()     = {} (2 palavras: endereço + vtable)",
+        mem::size_of::<&dyn Display>()
+    )?;
+    let shown: Box<dyn Display> = Box::new(age);
+    let data = &*shown as *const dyn Display as *const ();
+    writeln!(
+        out,
+        "Box<dyn Display> da idade ({}) : dados em {}",
+        shown,
+        pointer_address(data)
+    )?;
+    writeln!(
+        out,
+        "(a segunda palavra do Box aponta para a vtable de i32: Display::fmt, drop, size, align)"
+    )
+}
+
+/// Otimização de nicho: `None` reaproveita um valor inválido do tipo (o ponteiro nulo).
+pub fn demo_niche(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Otimização de nicho (Option<Box<T>>)")?;
+    let boxed = mem::size_of::<Box<i32>>();
+    let opt_boxed = mem::size_of::<Option<Box<i32>>>();
+    // garantido pela linguagem: Box nunca é nulo, então None = ponteiro nulo
+    assert_eq!(boxed, opt_boxed);
+    writeln!(out, "size_of::<Box<i32>>()         = {}", boxed)?;
+    writeln!(
+        out,
+        "size_of::<Option<Box<i32>>>() = {} (igual: None é o ponteiro nulo, sem tag)",
+        opt_boxed
+    )?;
+    writeln!(
+        out,
+        "size_of::<i32>()              = {}",
+        mem::size_of::<i32>()
+    )?;
+    writeln!(
+        out,
+        "size_of::<Option<i32>>()      = {} (maior: todo i32 é válido, precisa de tag + padding)",
+        mem::size_of::<Option<i32>>()
+    )?;
+    writeln!(
+        out,
+        "(ganham o nicho: Box, &T, &mut T, NonNull, NonZero*; não ganham: i32, u64, usize...)"
+    )
+}
+
+/// Mover copia só o handle (ptr, len, cap) para um novo lugar na stack; o
+/// buffer no heap fica onde estava.
+pub fn demo_move(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Move: o handle muda de lugar, o heap não")?;
+    let original = String::from("movido");
+    writeln!(
+        out,
+        "antes : String struct (stack) = {}, buffer (heap) = {}",
+        pointer_address(&original),
+        pointer_address(original.as_ptr())
+    )?;
+    let moved = original;
+    writeln!(
+        out,
+        "depois: String struct (stack) = {}, buffer (heap) = {}",
+        pointer_address(&moved),
+        pointer_address(moved.as_ptr())
+    )?;
+
+    let boxed = Box::new(42i32);
+    writeln!(
+        out,
+        "antes : Box (stack) = {}, pointee (heap) = {}",
+        pointer_address(&boxed),
+        pointer_address(&*boxed)
+    )?;
+    let moved_box = boxed;
+    writeln!(
+        out,
+        "depois: Box (stack) = {}, pointee (heap) = {}",
+        pointer_address(&moved_box),
+        pointer_address(&*moved_box)
+    )?;
+    writeln!(
+        out,
+        "(o move copia as palavras do handle — 3 no String, 1 no Box — para a nova variável;"
+    )?;
+    writeln!(
+        out,
+        " o valor do ponteiro copiado é o mesmo, então o endereço no heap é estável)"
+    )
+}
+
+/// `mem::swap` troca os handles de dois `String`; os buffers não saem do lugar.
+pub fn demo_swap(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "mem::swap")?;
+    let mut a = String::from("primeiro");
+    let mut b = String::from("segundo");
+    writeln!(
+        out,
+        "antes : a = {:<9} buffer {} | b = {:<9} buffer {}",
+        a,
+        pointer_address(a.as_ptr()),
+        b,
+        pointer_address(b.as_ptr())
+    )?;
+    mem::swap(&mut a, &mut b);
+    writeln!(
+        out,
+        "depois: a = {:<9} buffer {} | b = {:<9} buffer {}",
+        a,
+        pointer_address(a.as_ptr()),
+        b,
+        pointer_address(b.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "(swap troca só os campos ptr/len/cap na stack; nenhum byte dos buffers é copiado)"
+    )
+}
+
+/// Recursão até `depth` níveis, medindo a distância entre os frames.
+pub fn demo_recursion(out: &mut dyn Write, depth: usize) -> io::Result<()> {
+    section(out, "Recursão: consumo de stack por frame")?;
+    let mut addrs = Vec::with_capacity(depth);
+    recurse(depth, &mut addrs);
+    let Some(&base) = addrs.first() else {
+        return writeln!(out, "(profundidade 0: nada a medir)");
+    };
+    writeln!(
+        out,
+        "{:>5} {:>18} {:>10} {:>10}",
+        "nível", "local", "delta", "total"
+    )?;
+    let mut previous = base;
+    for (level, &addr) in addrs.iter().enumerate() {
+        // a stack cresce para baixo nos alvos comuns; abs_diff evita depender disso
+        writeln!(
+            out,
+            "{:>5} {:>18} {:>10} {:>10}",
+            level + 1,
+            hex_address(addr),
+            previous.abs_diff(addr),
+            base.abs_diff(addr)
+        )?;
+        previous = addr;
+    }
+    if addrs.len() > 1 {
+        let per_frame = base.abs_diff(previous) / (addrs.len() - 1);
+        writeln!(out, "≈ {} bytes de stack por frame", per_frame)?;
+    }
+    Ok(())
+}
+
+/// registra o endereço de um local em cada nível; `inline(never)` garante
+/// um frame de verdade por chamada
+#[inline(never)]
+fn recurse(remaining: usize, addrs: &mut Vec<usize>) {
+    if remaining == 0 {
+        return;
+    }
+    let local = remaining;
+    addrs.push(std::hint::black_box(&local) as *const usize as usize);
+    recurse(remaining - 1, addrs);
+}
+
+/// argumento interno que faz o binário executar [`overflow_child`]
+pub const OVERFLOW_CHILD_FLAG: &str = "--overflow-child";
+
+/// tamanho da stack da thread que vai estourar
+const OVERFLOW_STACK_SIZE: usize = 64 * 1024;
+
+/// Estouro de pilha de verdade, isolado em um processo filho.
+///
+/// Em Rust um estouro de pilha aborta o *processo* inteiro, mesmo vindo de
+/// uma thread secundária; não há como capturá-lo por `join`. Por isso o
+/// binário roda a si mesmo com [`OVERFLOW_CHILD_FLAG`] e observa o filho
+/// morrer, enquanto este processo continua.
+pub fn demo_overflow(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Estouro de pilha controlado")?;
+    writeln!(
+        out,
+        "ATENÇÃO: um processo filho vai estourar a pilha de propósito (thread com {} KiB).",
+        OVERFLOW_STACK_SIZE / 1024
+    )?;
+    let exe = env::current_exe()?;
+    let child = Command::new(exe).arg(OVERFLOW_CHILD_FLAG).output()?;
+    let stderr = String::from_utf8_lossy(&child.stderr);
+    if !child.status.success() && stderr.contains("overflow") {
+        writeln!(out, "estouro de pilha detectado na thread")?;
+        if let Some(line) = stderr.lines().find(|l| l.contains("overflow")) {
+            writeln!(out, "mensagem do runtime: {}", line.trim())?;
+        }
+        writeln!(out, "status do filho: {}", child.status)
+    } else {
+        writeln!(
+            out,
+            "o filho terminou sem estouro detectável (status: {})",
+            child.status
+        )
+    }
+}
+
+/// Ponto de entrada do processo filho: recursão sem fim em uma thread com
+/// stack pequena. Não retorna normalmente — o runtime aborta o processo.
+pub fn overflow_child() -> ! {
+    let handle = thread::Builder::new()
+        .name("overflow".into())
+        .stack_size(OVERFLOW_STACK_SIZE)
+        .spawn(|| unbounded(0))
+        .expect("falha ao criar a thread");
+    let _ = handle.join();
+    std::process::exit(1);
+}
+
+/// recursão sem caso base; o array e o `black_box` impedem que vire um laço
+#[allow(unconditional_recursion)]
+#[inline(never)]
+fn unbounded(level: u64) -> u64 {
+    let pad = std::hint::black_box([level; 32]);
+    unbounded(level + 1) + pad[0]
+}
+
+/// `Cell`/`RefCell`: mutação através de `&`, com a checagem de empréstimos
+/// movida para o tempo de execução.
+pub fn demo_interior_mut(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Mutabilidade interior (Cell / RefCell)")?;
+    let counter = Cell::new(1i32);
+    let shared = &counter;
+    writeln!(
+        out,
+        "Cell<i32>      antes  = {:<10} em {}",
+        shared.get(),
+        pointer_address(shared)
+    )?;
+    shared.set(shared.get() + 41);
+    writeln!(
+        out,
+        "Cell<i32>      depois = {:<10} em {}",
+        shared.get(),
+        pointer_address(shared)
+    )?;
+
+    let text = RefCell::new(String::from("antes"));
+    let shared = &text;
+    writeln!(
+        out,
+        "RefCell<String> antes  = {:<9} em {}",
+        shared.borrow(),
+        pointer_address(shared)
+    )?;
+    shared.borrow_mut().push_str("+depois");
+    writeln!(
+        out,
+        "RefCell<String> depois = {:<9} em {}",
+        shared.borrow(),
+        pointer_address(shared)
+    )?;
+
+    // segundo borrow_mut com o primeiro ainda vivo: borrow_mut() entraria em panic
+    let first = shared.borrow_mut();
+    match shared.try_borrow_mut() {
+        Ok(_) => writeln!(out, "segundo borrow_mut aceito (inesperado)")?,
+        Err(e) => writeln!(out, "segundo borrow_mut recusado em runtime: {}", e)?,
+    }
+    drop(first);
+    writeln!(
+        out,
+        "(o container não muda de endereço; a regra \"um &mut por vez\" é verificada em runtime)"
+    )
+}
+
+/// nó que aponta para o outro com `Rc` (forte)
+struct StrongNode {
+    _tracer: Tracer,
+    other: RefCell<Option<Rc<StrongNode>>>,
+}
+
+/// nó com um lado forte (`next`) e o outro fraco (`prev`)
+struct WeakNode {
+    _tracer: Tracer,
+    next: RefCell<Option<Rc<WeakNode>>>,
+    prev: RefCell<Weak<WeakNode>>,
+}
+
+/// Ciclo de `Rc` vaza; trocar um dos lados por `Weak` quebra o ciclo.
+pub fn demo_weak(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Weak: quebrando ciclos de Rc")?;
+
+    // 1) a <-> b, ambos fortes: cada um mantém o outro vivo
+    let mut counts = (0, 0, 0, 0);
+    let drops = tracer::capture_drops(|| {
+        let a = Rc::new(StrongNode {
+            _tracer: Tracer("ciclo forte: a"),
+            other: RefCell::new(None),
+        });
+        let b = Rc::new(StrongNode {
+            _tracer: Tracer("ciclo forte: b"),
+            other: RefCell::new(Some(Rc::clone(&a))),
+        });
+        *a.other.borrow_mut() = Some(Rc::clone(&b));
+        counts = (
+            Rc::strong_count(&a),
+            Rc::weak_count(&a),
+            Rc::strong_count(&b),
+            Rc::weak_count(&b),
+        );
+    });
+    writeln!(
+        out,
+        "Rc <-> Rc : a strong={} weak={}, b strong={} weak={}",
+        counts.0, counts.1, counts.2, counts.3
+    )?;
+    writeln!(
+        out,
+        "  drops ao sair do escopo: {:?} (vazou: um segura o outro)",
+        drops
+    )?;
+
+    // 2) a -> b forte, b -> a fraco: ao soltar a, tudo é liberado
+    let drops = tracer::capture_drops(|| {
+        let a = Rc::new(WeakNode {
+            _tracer: Tracer("com Weak: a"),
+            next: RefCell::new(None),
+            prev: RefCell::new(Weak::new()),
+        });
+        let b = Rc::new(WeakNode {
+            _tracer: Tracer("com Weak: b"),
+            next: RefCell::new(None),
+            prev: RefCell::new(Rc::downgrade(&a)),
+        });
+        *a.next.borrow_mut() = Some(Rc::clone(&b));
+        counts = (
+            Rc::strong_count(&a),
+            Rc::weak_count(&a),
+            Rc::strong_count(&b),
+            Rc::weak_count(&b),
+        );
+        // o Weak só "sobe" para Rc enquanto a ainda existe
+        debug_assert!(b.prev.borrow().upgrade().is_some());
+    });
+    writeln!(
+        out,
+        "Rc -> Weak: a strong={} weak={}, b strong={} weak={}",
+        counts.0, counts.1, counts.2, counts.3
+    )?;
+    writeln!(out, "  drops ao sair do escopo: {:?}", drops)?;
+    writeln!(
+        out,
+        "(o ciclo forte fica no heap até o fim do processo; Weak não conta para manter vivo)"
+    )
+}
+
+/// Troca espaços por `_`; só aloca um `String` novo se houver algo a trocar.
+pub fn underscore_spaces(input: &str) -> Cow<'_, str> {
+    if input.contains(' ') {
+        Cow::Owned(input.replace(' ', "_"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// `Cow<str>`: empresta quando nada muda, aloca só quando precisa.
+pub fn demo_cow(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Cow<str>: emprestado vs próprio")?;
+    for input in [name, "Ana", "Ana Maria"] {
+        let result = underscore_spaces(input);
+        let kind = match &result {
+            Cow::Borrowed(_) => "Cow::Borrowed (reusa o buffer da entrada)",
+            Cow::Owned(_) => "Cow::Owned (novo String no heap)",
+        };
+        writeln!(
+            out,
+            "{:<12} entrada {} -> {:<12} {}  {}",
+            format!("{:?}", input),
+            pointer_address(input.as_ptr()),
+            result,
+            pointer_address(result.as_ptr()),
+            kind
+        )?;
+    }
+    Ok(())
+}
+
+/// Os bytes do buffer do nome, lado a lado com seus endereços no heap.
+pub fn demo_hexdump(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Hexdump do buffer do nome")?;
+    let name = name.to_string();
+    writeln!(
+        out,
+        "name.as_ptr() = {}, len = {} bytes (UTF-8)",
+        pointer_address(name.as_ptr()),
+        name.len()
+    )?;
+    hexdump(name.as_bytes(), name.as_ptr() as usize, out)
+}
+
+/// `#[repr(C)]`: campos na ordem declarada, com padding para alinhar `b`.
+#[repr(C)]
+pub struct ReprCExample {
+    pub a: u8,
+    pub b: u32,
+    pub c: u8,
+}
+
+/// Mesmos campos sem padding algum (`b` fica desalinhado).
+#[repr(C, packed)]
+pub struct PackedExample {
+    pub a: u8,
+    pub b: u32,
+    pub c: u8,
+}
+
+/// mapa de bytes: a letra do campo em cada byte ocupado, `.` no padding
+fn byte_map(size: usize, fields: &[(char, usize, usize)]) -> String {
+    (0..size)
+        .map(|i| {
+            fields
+                .iter()
+                .find(|&&(_, offset, len)| (offset..offset + len).contains(&i))
+                .map_or('.', |&(name, _, _)| name)
+        })
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Deslocamento de cada campo (`offset_of!`) e o padding que sobra.
+pub fn demo_layout(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Layout de struct: repr(C) vs packed")?;
+    let c_fields = [
+        ('a', mem::offset_of!(ReprCExample, a), 1),
+        ('b', mem::offset_of!(ReprCExample, b), 4),
+        ('c', mem::offset_of!(ReprCExample, c), 1),
+    ];
+    let packed_fields = [
+        ('a', mem::offset_of!(PackedExample, a), 1),
+        ('b', mem::offset_of!(PackedExample, b), 4),
+        ('c', mem::offset_of!(PackedExample, c), 1),
+    ];
+    for (label, fields, size, align) in [
+        (
+            "repr(C)",
+            &c_fields,
+            mem::size_of::<ReprCExample>(),
+            mem::align_of::<ReprCExample>(),
+        ),
+        (
+            "repr(C, packed)",
+            &packed_fields,
+            mem::size_of::<PackedExample>(),
+            mem::align_of::<PackedExample>(),
+        ),
+    ] {
+        writeln!(
+            out,
+            "{:<16} offsets a={} b={} c={} | size_of = {:>2}, align_of = {}",
+            label, fields[0].1, fields[1].1, fields[2].1, size, align
+        )?;
+        writeln!(out, "{:<16} bytes: {}", "", byte_map(size, fields))?;
+    }
+    writeln!(
+        out,
+        "(em repr(C) há 3 bytes de padding depois de `a` para alinhar `b` em 4, e 3 no fim;"
+    )?;
+    writeln!(
+        out,
+        " packed elimina o padding, mas ler `b` desalinhado pode ser lento ou exigir cópia)"
+    )
+}
+
+/// struct sem campos: tipo de tamanho zero
+struct Empty;
+
+/// Tipos de tamanho zero (ZST) não ocupam memória nem alocam no heap.
+pub fn demo_zst(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Tipos de tamanho zero (ZST)")?;
+    let unit_a = ();
+    let unit_b = ();
+    let empty_a = Empty;
+    let empty_b = Empty;
+    writeln!(
+        out,
+        "size_of::<()>() = {}, size_of::<Empty>() = {}",
+        mem::size_of::<()>(),
+        mem::size_of::<Empty>()
+    )?;
+    writeln!(
+        out,
+        "&unit_a  = {}, &unit_b  = {}",
+        pointer_address(&unit_a),
+        pointer_address(&unit_b)
+    )?;
+    writeln!(
+        out,
+        "&empty_a = {}, &empty_b = {}",
+        pointer_address(&empty_a),
+        pointer_address(&empty_b)
+    )?;
+
+    let mut units: Vec<()> = Vec::new();
+    let before = units.as_ptr();
+    for _ in 0..1_000_000 {
+        units.push(());
+    }
+    writeln!(
+        out,
+        "Vec<()>: len = {}, capacity = {}, as_ptr antes = {}, depois = {}",
+        units.len(),
+        units.capacity(),
+        pointer_address(before),
+        pointer_address(units.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "(um milhão de elementos sem nenhum byte no heap: o ponteiro é \"pendurado\" mas alinhado;"
+    )?;
+    writeln!(
+        out,
+        " referências a ZSTs podem compartilhar esse endereço sentinela)"
+    )
+}
+
+/// Closures: capturar por referência guarda só um ponteiro; `move` guarda o
+/// próprio handle do `String` (o buffer no heap continua o mesmo); sem
+/// captura, a closure não tem tamanho e pode virar um ponteiro de função.
+pub fn demo_closures(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Closures: captura por referência vs move")?;
+    let text = String::from("capturada");
+    let buffer = text.as_ptr();
+    writeln!(
+        out,
+        "text: size_of::<String>() = {}, buffer = {}",
+        mem::size_of::<String>(),
+        pointer_address(buffer)
+    )?;
+
+    let by_ref = || text.len();
+    writeln!(
+        out,
+        "|| text.len()         -> size_of_val = {:>2} (guarda um &String), resultado = {}",
+        mem::size_of_val(&by_ref),
+        by_ref()
+    )?;
+
+    let by_move = move || text.as_ptr();
+    writeln!(
+        out,
+        "move || text.as_ptr() -> size_of_val = {:>2} (guarda o String), buffer = {} (mesmo: {})",
+        mem::size_of_val(&by_move),
+        pointer_address(by_move()),
+        by_move() == buffer
+    )?;
+
+    let no_capture = |x: usize| x + 1;
+    let as_fn: fn(usize) -> usize = no_capture;
+    writeln!(
+        out,
+        "|x| x + 1             -> size_of_val = {:>2}; como fn(usize) -> usize = {:>2} (ponteiro de função)",
+        mem::size_of_val(&no_capture),
+        mem::size_of_val(&as_fn)
+    )?;
+    writeln!(
+        out,
+        "(cada closure é uma struct anônima com os campos capturados; o move só copia o handle, não o buffer)"
+    )
+}
+
+/// `&name` e `name.as_ptr()` são endereços diferentes: o primeiro é onde o
+/// handle do `String` mora (stack), o segundo é o buffer para onde ele aponta
+/// (heap).
+pub fn demo_handle(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "&name vs name.as_ptr()")?;
+    let name = name.to_string();
+    let handle = &name as *const String as usize;
+    let buffer = name.as_ptr() as usize;
+    writeln!(
+        out,
+        "&name         = {}  (o handle String, na stack)",
+        hex_address(handle)
+    )?;
+    writeln!(
+        out,
+        "name.as_ptr() = {}  (o buffer com os bytes, no heap)",
+        hex_address(buffer)
+    )?;
+    writeln!(
+        out,
+        "distância     = {} bytes (regiões diferentes, nada a ver uma com a outra)",
+        handle.abs_diff(buffer)
+    )?;
+    // as três palavras do handle, lidas pelos métodos públicos (sem transmute):
+    // a ordem dos campos dentro do String não é garantida, os valores sim
+    writeln!(
+        out,
+        "handle ({} bytes) = {{ ptr: {}, len: {}, cap: {} }}",
+        mem::size_of::<String>(),
+        hex_address(name.as_ptr() as usize),
+        name.len(),
+        name.capacity()
+    )?;
+    writeln!(
+        out,
+        "(copiar o handle copia só essas três palavras; o buffer continua onde está)"
+    )
+}
+
+/// `Box::into_raw` seguido de `Box::from_raw`: devolve o ponteiro cru, o
+/// valor lido através dele e o `Box` reconstituído (mesma alocação).
+pub fn raw_round_trip<T: Copy>(boxed: Box<T>) -> (*const T, T, Box<T>) {
+    let raw: *mut T = Box::into_raw(boxed);
+    // SAFETY: `raw` veio de `Box::into_raw` agora há pouco, então é não nulo,
+    // alinhado e aponta para um `T` vivo; `from_raw` é chamado exatamente uma
+    // vez, devolvendo a posse da alocação para um `Box` (sem double free).
+    unsafe {
+        let read = *raw;
+        (raw, read, Box::from_raw(raw))
+    }
+}
+
+/// Ida e volta de um `Box<i32>` por um ponteiro cru: enquanto é só `*mut`,
+/// ninguém libera a memória por nós.
+pub fn demo_raw_box(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Box::into_raw / Box::from_raw")?;
+    let boxed = Box::new(2025i32);
+    let original = &*boxed as *const i32;
+    writeln!(
+        out,
+        "Box::new(2025)     -> valor no heap em {}",
+        pointer_address(original)
+    )?;
+    let (raw, read, back) = raw_round_trip(boxed);
+    writeln!(
+        out,
+        "Box::into_raw      -> {} (mesmo endereço: {})",
+        pointer_address(raw),
+        raw == original
+    )?;
+    writeln!(out, "unsafe {{ *raw }}    -> {}", read)?;
+    writeln!(
+        out,
+        "Box::from_raw(raw) -> Box de novo, *back = {} (liberado no drop, como qualquer Box)",
+        back
+    )?;
+    writeln!(
+        out,
+        "(sem o from_raw a alocação vazaria: um *mut não tem destrutor)"
+    )
+}
+
+/// Vazamento proposital com `mem::forget`: o `Box` some sem rodar o drop e
+/// os bytes nunca voltam ao alocador. Com `--alloc-stats` o contador de bytes
+/// vivos mostra isso.
+pub fn demo_leak(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Vazamento proposital com mem::forget")?;
+    writeln!(
+        out,
+        "AVISO: esta demo vaza memória de propósito, só para ensinar (1 KiB por execução)"
+    )?;
+    let before = alloc_stats::snapshot();
+    let leaked = Box::new([0u8; 1024]);
+    let addr = leaked.as_ptr();
+    mem::forget(leaked);
+    let after = alloc_stats::snapshot();
+    writeln!(
+        out,
+        "Box<[u8; 1024]> no heap em {}; mem::forget(leaked) -> drop nunca roda",
+        pointer_address(addr)
+    )?;
+    if alloc_stats::is_enabled() {
+        writeln!(
+            out,
+            "bytes vivos: antes = {}, depois = {} (+{}, nunca liberados)",
+            before.live(),
+            after.live(),
+            after.live().saturating_sub(before.live())
+        )
+    } else {
+        writeln!(
+            out,
+            "(rode com --alloc-stats para ver o contador de bytes vivos subir e não descer)"
+        )
+    }
+}
+
+/// enche `v` com `n` elementos contando quantas vezes a capacidade mudou
+/// (cada mudança é uma realocação)
+fn push_counting_reallocs(mut v: Vec<u32>, n: u32) -> (Vec<u32>, usize) {
+    let mut reallocs = 0;
+    let mut capacity = v.capacity();
+    for i in 0..n {
+        v.push(i);
+        if v.capacity() != capacity {
+            capacity = v.capacity();
+            reallocs += 1;
+        }
+    }
+    (v, reallocs)
+}
+
+/// O mesmo `Vec<u32>` de 1000 elementos crescendo sozinho vs pré-dimensionado
+/// com `with_capacity`: realocações e tempo.
+pub fn demo_capacity_strategies(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Vec::new() vs Vec::with_capacity(1000)")?;
+    const N: u32 = 1000;
+    // Vec::with_capacity(0) é o mesmo que Vec::new(): nada alocado ainda
+    let strategies = [("Vec::new()", 0), ("Vec::with_capacity(1000)", N as usize)];
+    for (label, capacity) in strategies {
+        let start = Instant::now();
+        let (v, reallocs) = push_counting_reallocs(Vec::with_capacity(capacity), N);
+        let elapsed = start.elapsed();
+        writeln!(
+            out,
+            "{:<25}: len = {}, capacity = {:>4}, realocações = {:>2}, tempo = {:?}",
+            label,
+            v.len(),
+            v.capacity(),
+            reallocs,
+            elapsed
+        )?;
+    }
+    writeln!(
+        out,
+        "(com a capacidade certa só existe a alocação inicial; crescendo, cada realocação copia tudo)"
+    )
+}
+
+/// Enum com variantes de formatos diferentes (unit, tupla, struct).
+pub enum Mixed {
+    Empty,
+    Number(u64),
+    Pair { a: u8, b: u8 },
+}
+
+/// Enum estilo C com discriminante explícito de 1 byte.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum Color {
+    Red = 1,
+    Green = 2,
+    Blue = 4,
+}
+
+/// Tamanho do enum vs sua maior variante: o discriminante se soma ao payload
+/// e o total é arredondado para o alinhamento do campo mais exigente.
+pub fn demo_enum_layout(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Layout de enums e discriminantes")?;
+    writeln!(
+        out,
+        "enum Mixed {{ Empty, Number(u64), Pair {{ a: u8, b: u8 }} }}: size = {}, align = {}",
+        mem::size_of::<Mixed>(),
+        mem::align_of::<Mixed>()
+    )?;
+    writeln!(
+        out,
+        "maior variante: Number(u64) = {} bytes; Pair = {}; Empty = 0",
+        mem::size_of::<u64>(),
+        mem::size_of::<(u8, u8)>()
+    )?;
+    writeln!(
+        out,
+        "(+{} bytes: o discriminante ocupa 1 byte, mas o u64 exige alinhamento {} e o resto vira padding)",
+        mem::size_of::<Mixed>() - mem::size_of::<u64>(),
+        mem::align_of::<u64>()
+    )?;
+    writeln!(
+        out,
+        "#[repr(u8)] enum Color: size = {}; Red as u8 = {}, Green as u8 = {}, Blue as u8 = {}",
+        mem::size_of::<Color>(),
+        Color::Red as u8,
+        Color::Green as u8,
+        Color::Blue as u8
+    )?;
+    writeln!(
+        out,
+        "(sem payload o enum é só o discriminante; repr(u8) fixa o tipo dele)"
+    )
+}
+
+/// Struct auto-referente: `self_ptr` aponta para o próprio campo `data`.
+/// Se o valor se mover, `self_ptr` continua apontando para o endereço antigo.
+struct SelfRef {
+    data: String,
+    self_ptr: *const String,
+    // tira o `Unpin` automático: com Pin, o valor não pode mais sair do lugar
+    _pin: PhantomPinned,
+}
+
+impl SelfRef {
+    fn new(data: &str) -> Self {
+        SelfRef {
+            data: data.to_string(),
+            self_ptr: ptr::null(),
+            _pin: PhantomPinned,
+        }
+    }
+
+    /// o ponteiro ainda aponta para o próprio campo?
+    fn is_consistent(&self) -> bool {
+        ptr::eq(self.self_ptr, &self.data)
+    }
+}
+
+/// `Pin<Box<T>>` garante que o valor no heap nunca se move, então a
+/// auto-referência continua válida mesmo quando o handle muda de dono.
+pub fn demo_pin(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Pin e dados auto-referentes")?;
+
+    // sem Pin: inicializamos a auto-referência e depois movemos o valor
+    let mut loose = SelfRef::new("solto");
+    loose.self_ptr = &loose.data;
+    let before = &loose as *const SelfRef;
+    let moved = loose;
+    writeln!(
+        out,
+        "sem Pin: {} -> {} após o move; self_ptr ainda válido? {}",
+        pointer_address(before),
+        pointer_address(&moved as *const SelfRef),
+        moved.is_consistent()
+    )?;
+
+    // com Pin: o valor vai para o heap e fica lá
+    let mut pinned = Box::pin(SelfRef::new("fixo"));
+    // SAFETY: não movemos o valor para fora do Pin; só escrevemos um campo
+    // no lugar. Invariante: depois desta linha `self_ptr` aponta para `data`
+    // e continua válido porque o `Pin` proíbe (em código seguro) qualquer
+    // move do `SelfRef` enquanto ele existir.
+    unsafe {
+        let this = pinned.as_mut().get_unchecked_mut();
+        this.self_ptr = &this.data;
+    }
+    let at = |p: &Pin<Box<SelfRef>>| &**p as *const SelfRef;
+    writeln!(
+        out,
+        "com Pin: valor em {}, self_ptr ainda válido? {}",
+        pointer_address(at(&pinned)),
+        pinned.is_consistent()
+    )?;
+    let handed_over = pinned; // o handle (Box) se move; o valor no heap não
+    writeln!(
+        out,
+        "handle movido: valor em {}, self_ptr ainda válido? {}",
+        pointer_address(at(&handed_over)),
+        handed_over.is_consistent()
+    )?;
+    let stored = [handed_over];
+    writeln!(
+        out,
+        "guardado num array: valor em {}, self_ptr ainda válido? {}",
+        pointer_address(at(&stored[0])),
+        stored[0].is_consistent()
+    )?;
+    writeln!(
+        out,
+        "(com PhantomPinned, mem::swap/mem::replace no valor não compilam: só o ponteiro viaja)"
+    )
+}
+
+/// onde estão o handle da chave (dentro da tabela) e o buffer dela (heap)
+fn key_addresses(map: &HashMap<String, i32>, key: &str) -> (*const String, *const u8) {
+    let (k, _) = map.get_key_value(key).expect("chave inserida antes");
+    (k as *const String, k.as_ptr())
+}
+
+/// `HashMap` é um handle pequeno na stack com uma tabela no heap; as chaves
+/// `String` têm, cada uma, seu próprio buffer em outro lugar do heap.
+pub fn demo_hashmap(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "HashMap no heap")?;
+    let mut map: HashMap<String, i32> = HashMap::new();
+    writeln!(
+        out,
+        "HashMap::new(): size_of (handle) = {}, capacity = {} (alocou? {})",
+        mem::size_of::<HashMap<String, i32>>(),
+        map.capacity(),
+        map.capacity() > 0
+    )?;
+    for (i, key) in ["ana", "bia", "caio"].into_iter().enumerate() {
+        map.insert(key.to_string(), i as i32);
+    }
+    let capacity = map.capacity();
+    let (ana_slot, ana_buf) = key_addresses(&map, "ana");
+    let (bia_slot, bia_buf) = key_addresses(&map, "bia");
+    writeln!(
+        out,
+        "3 chaves: len = {}, capacity = {} (alocou? {})",
+        map.len(),
+        capacity,
+        map.capacity() > 0
+    )?;
+    writeln!(
+        out,
+        "  \"ana\": String na tabela em {}, buffer em {}",
+        pointer_address(ana_slot),
+        pointer_address(ana_buf)
+    )?;
+    writeln!(
+        out,
+        "  \"bia\": String na tabela em {}, buffer em {}",
+        pointer_address(bia_slot),
+        pointer_address(bia_buf)
+    )?;
+
+    // passar da capacidade (fator de carga) obriga a tabela a crescer
+    for i in 0..capacity * 2 {
+        map.insert(format!("extra{}", i), i as i32);
+    }
+    let (ana_slot2, ana_buf2) = key_addresses(&map, "ana");
+    writeln!(
+        out,
+        "{} chaves: capacity = {} (a tabela foi realocada)",
+        map.len(),
+        map.capacity()
+    )?;
+    writeln!(
+        out,
+        "  \"ana\": String na tabela em {} (moveu? {}), buffer em {} (moveu? {})",
+        pointer_address(ana_slot2),
+        ana_slot2 != ana_slot,
+        pointer_address(ana_buf2),
+        ana_buf2 != ana_buf
+    )?;
+    writeln!(
+        out,
+        "(ao crescer, os handles String são copiados para a tabela nova; os buffers das chaves ficam onde estavam)"
+    )
+}
+
+/// um único objeto com endereço fixo durante todo o programa
+static GREETING: &str = "olá da static";
+/// sem endereço próprio: o valor é copiado em cada uso
+const LIMIT: i32 = 100;
+
+#[inline(never)]
+fn greeting_from_site_a() -> *const &'static str {
+    &GREETING
+}
+
+#[inline(never)]
+fn greeting_from_site_b() -> *const &'static str {
+    &GREETING
+}
+
+/// `static` é um lugar na memória; `const` é um valor colado em cada uso.
+pub fn demo_static_const(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "static vs const")?;
+    let (a, b) = (greeting_from_site_a(), greeting_from_site_b());
+    writeln!(
+        out,
+        "&GREETING (static) na função a = {}, na função b = {} (mesmo lugar: {})",
+        pointer_address(a),
+        pointer_address(b),
+        a == b
+    )?;
+
+    // cada uso de LIMIT vira uma cópia; pegar o endereço força um temporário
+    let first = LIMIT;
+    let second = LIMIT;
+    writeln!(
+        out,
+        "let first = LIMIT -> {}; let second = LIMIT -> {} (mesmo lugar: {})",
+        pointer_address(&first),
+        pointer_address(&second),
+        ptr::eq(&first, &second)
+    )?;
+    writeln!(
+        out,
+        "&LIMIT direto = {} (o compilador pode promover o temporário a um estático anônimo)",
+        pointer_address(&LIMIT)
+    )?;
+    writeln!(
+        out,
+        "(use static quando precisar de um endereço único; const é só um valor com nome)"
+    )
+}
+
+/// argumento interno que faz o binário executar [`code_address_child`]
+pub const CODE_ADDRESS_CHILD_FLAG: &str = "--code-address-child";
+
+/// Ponto de entrada do processo filho da demo de ASLR: imprime o endereço de
+/// [`crate::example_function`] e sai.
+pub fn code_address_child() -> ! {
+    println!("{:#x}", crate::example_function as *const () as usize);
+    process::exit(0)
+}
+
+/// roda o próprio binário com [`CODE_ADDRESS_CHILD_FLAG`] e lê o endereço
+fn launch_code_address() -> Option<usize> {
+    let exe = env::current_exe().ok()?;
+    let child = Command::new(exe)
+        .arg(CODE_ADDRESS_CHILD_FLAG)
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&child.stdout);
+    usize::from_str_radix(text.trim().strip_prefix("0x")?, 16).ok()
+}
+
+/// Veredito de [`demo_aslr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AslrVerdict {
+    /// o endereço mudou entre os processos
+    Active,
+    /// os três processos viram o mesmo endereço
+    Inactive,
+    /// algum relançamento falhou
+    Unknown,
+}
+
+/// Compara o endereço de `example_function` neste processo (`here`) com o de
+/// dois relançamentos (`None` = o lançamento falhou).
+pub fn aslr_verdict(here: usize, first: Option<usize>, second: Option<usize>) -> AslrVerdict {
+    match (first, second) {
+        (Some(first), Some(second)) if first != second || first != here => AslrVerdict::Active,
+        (Some(_), Some(_)) => AslrVerdict::Inactive,
+        _ => AslrVerdict::Unknown,
+    }
+}
+
+/// ASLR só aparece de um processo para outro: dentro de um processo o código
+/// nunca muda de lugar. Por isso lançamos o binário duas vezes e comparamos o
+/// endereço de `example_function` em cada lançamento.
+///
+/// Fica fora do [`registry`] (relançar o binário a cada execução é caro e,
+/// sob `cargo test`, `current_exe` é o executor de testes); roda só pelo
+/// subcomando `aslr`.
+pub fn demo_aslr(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "ASLR: o código muda de lugar entre execuções?")?;
+    let here = crate::example_function as *const () as usize;
+    writeln!(
+        out,
+        "este processo : example_function = {}",
+        hex_address(here)
+    )?;
+    let (first, second) = (launch_code_address(), launch_code_address());
+    for (i, addr) in [first, second].into_iter().enumerate() {
+        if let Some(addr) = addr {
+            writeln!(
+                out,
+                "lançamento {}  : example_function = {}",
+                i + 1,
+                hex_address(addr)
+            )?;
+        }
+    }
+    match aslr_verdict(here, first, second) {
+        AslrVerdict::Active => writeln!(out, "ASLR provavelmente ativo")?,
+        AslrVerdict::Inactive => writeln!(out, "ASLR provavelmente inativo")?,
+        AslrVerdict::Unknown => writeln!(
+            out,
+            "não foi possível relançar o binário para comparar (ASLR indeterminado)"
+        )?,
+    }
+    writeln!(
+        out,
+        "(um processo não vê a própria randomização: ela acontece ao carregar o executável)"
+    )
+}
+
+/// Cria `Box<Box<Box<i32>>>` e devolve o endereço de cada nível no heap
+/// (de fora para dentro) e o valor alcançado após as três derreferências.
+pub fn nested_box_levels(value: i32) -> ([usize; 3], i32) {
+    let outer: Box<Box<Box<i32>>> = Box::new(Box::new(Box::new(value)));
+    let level1 = &*outer as *const Box<Box<i32>> as usize;
+    let level2 = &**outer as *const Box<i32> as usize;
+    let level3 = &***outer as *const i32 as usize;
+    ([level1, level2, level3], ***outer)
+}
+
+/// Cada camada de `Box` é uma alocação separada que guarda o ponteiro para a
+/// próxima: ler o valor é seguir três ponteiros.
+pub fn demo_nested_box(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Box<Box<Box<i32>>>: perseguindo ponteiros")?;
+    let ([level1, level2, level3], value) = nested_box_levels(7);
+    writeln!(
+        out,
+        "*outer   (Box<Box<i32>>, heap) = {}",
+        hex_address(level1)
+    )?;
+    writeln!(
+        out,
+        "**outer  (Box<i32>, heap)      = {}",
+        hex_address(level2)
+    )?;
+    writeln!(
+        out,
+        "***outer (i32, heap)           = {}",
+        hex_address(level3)
+    )?;
+    writeln!(out, "valor após três derreferências = {}", value)?;
+    writeln!(
+        out,
+        "(três alocações distintas; as duas primeiras guardam só um ponteiro de {} bytes)",
+        mem::size_of::<Box<i32>>()
+    )
+}
+
+/// `clear()` zera o `len` mas mantém a alocação; `shrink_to_fit()` devolve
+/// a memória e o próximo preenchimento precisa alocar de novo.
+pub fn demo_buffer_reuse(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Reuso de buffer: clear() vs shrink_to_fit()")?;
+    const N: usize = 256;
+    let mut buf: Vec<u8> = Vec::new();
+    let report = |out: &mut dyn Write, step: &str, buf: &[u8], capacity: usize| {
+        writeln!(
+            out,
+            "{:<25}: len = {:>3}, capacity = {:>3}, as_ptr = {}",
+            step,
+            buf.len(),
+            capacity,
+            pointer_address(buf.as_ptr())
+        )
+    };
+    buf.extend(std::iter::repeat_n(b'x', N));
+    report(out, "preenchido", &buf, buf.capacity())?;
+    let first = buf.as_ptr();
+    buf.clear();
+    report(out, "clear()", &buf, buf.capacity())?;
+    buf.extend(std::iter::repeat_n(b'y', N));
+    report(out, "preenchido de novo", &buf, buf.capacity())?;
+    writeln!(
+        out,
+        "(mesmo buffer após clear + refill: {})",
+        buf.as_ptr() == first
+    )?;
+
+    buf.clear();
+    buf.shrink_to_fit();
+    report(out, "clear() + shrink_to_fit()", &buf, buf.capacity())?;
+    buf.extend(std::iter::repeat_n(b'z', N));
+    report(out, "preenchido após shrink", &buf, buf.capacity())?;
+    writeln!(
+        out,
+        "(o shrink liberou a memória e o refill alocou de novo — o alocador pode até devolver o mesmo endereço)"
+    )
+}
+
+/// Um valor concreto derivado dos bytes no heap: o FNV-1a do buffer do nome
+/// muda assim que um único byte muda.
+pub fn demo_checksum(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Checksum (FNV-1a) do buffer do nome")?;
+    let name = name.to_string();
+    let start = name.as_ptr() as usize;
+    writeln!(
+        out,
+        "buffer: {}..{} ({} bytes), fnv1a = {:#018x}",
+        hex_address(start),
+        hex_address(start + name.len()),
+        name.len(),
+        fnv1a(name.as_bytes())
+    )?;
+
+    // um clone tem buffer próprio: mexer nele não afeta o original
+    let mut copy = name.clone().into_bytes();
+    match copy.first_mut() {
+        Some(first) => {
+            *first ^= 0x01;
+            writeln!(
+                out,
+                "clone com o 1º byte alterado ({:#04x} -> {:#04x}): fnv1a = {:#018x}",
+                name.as_bytes()[0],
+                copy[0],
+                fnv1a(&copy)
+            )?;
+        }
+        None => writeln!(out, "(nome vazio: nada para alterar)")?,
+    }
+    writeln!(
+        out,
+        "original de novo: fnv1a = {:#018x} (inalterado)",
+        fnv1a(name.as_bytes())
+    )
+}
+
+/// imprime a fatia `text[range]` e onde ela começa dentro do buffer, ou
+/// explica por que o intervalo não é válido (fora de uma fronteira de char)
+fn print_slice(
+    out: &mut dyn Write,
+    parent: &str,
+    text: &str,
+    range: std::ops::Range<usize>,
+) -> io::Result<()> {
+    let label = format!("{}[{}..{}]", parent, range.start, range.end);
+    match text.get(range.clone()) {
+        Some(slice) => writeln!(
+            out,
+            "{:<14} = {:<12} as_ptr = {} (buffer + {})",
+            label,
+            format!("{:?}", slice),
+            pointer_address(slice.as_ptr()),
+            slice.as_ptr() as usize - text.as_ptr() as usize
+        ),
+        None => writeln!(
+            out,
+            "{:<14} = erro: {} não cai numa fronteira de caractere UTF-8",
+            label,
+            if text.is_char_boundary(range.start) {
+                range.end
+            } else {
+                range.start
+            }
+        ),
+    }
+}
+
+/// Sub-fatias `&str` não copiam nada: apontam para dentro do mesmo buffer
+/// do `String`, cada uma num deslocamento diferente.
+pub fn demo_slices(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Fatias (&str) dentro do mesmo buffer")?;
+    let name = name.to_string();
+    writeln!(
+        out,
+        "name: buffer = {}, len = {} bytes, {} chars",
+        pointer_address(name.as_ptr()),
+        name.len(),
+        name.chars().count()
+    )?;
+    // metade em caracteres, convertida para o índice em bytes correspondente
+    let mid = name
+        .char_indices()
+        .nth(name.chars().count() / 2)
+        .map_or(name.len(), |(i, _)| i);
+    print_slice(out, "name", &name, 0..mid)?;
+    print_slice(out, "name", &name, mid..name.len())?;
+    // um corte ingênuo em bytes pode cair no meio de um caractere multibyte
+    // ("á" ocupa os bytes 2 e 3)
+    let accented = String::from("Olá");
+    print_slice(out, "\"Olá\"", &accented, 0..3)?;
+    writeln!(
+        out,
+        "(uma &str é só (ptr, len) emprestados; use get(..) para cortar sem risco de panic)"
+    )
+}
+
+/// endereço de cada elemento, pegando a referência por indexação segura
+pub fn element_addresses<T>(items: &[T]) -> Vec<usize> {
+    (0..items.len())
+        .map(|i| &items[i] as *const T as usize)
+        .collect()
+}
+
+/// Elementos de um `Vec` ficam lado a lado no buffer: o endereço do elemento
+/// `i` é `as_ptr() + i * size_of::<T>()`.
+pub fn demo_ptr_arithmetic(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Aritmética de ponteiros num Vec<i64>")?;
+    let values: Vec<i64> = vec![10, 20, 30, 40, 50];
+    let base = values.as_ptr() as usize;
+    let stride = mem::size_of::<i64>();
+    writeln!(
+        out,
+        "as_ptr() = {}, size_of::<i64>() = {}",
+        hex_address(base),
+        stride
+    )?;
+    let addrs = element_addresses(&values);
+    for (i, addr) in addrs.iter().enumerate() {
+        writeln!(
+            out,
+            "&values[{}] = {} = as_ptr() + {:>2} (valor {})",
+            i,
+            hex_address(*addr),
+            addr - base,
+            values[i]
+        )?;
+    }
+    let contiguous = addrs.windows(2).all(|w| w[1] - w[0] == stride);
+    writeln!(
+        out,
+        "passo constante de {} bytes entre vizinhos: {} (buffer contíguo no heap)",
+        stride, contiguous
+    )
+}
+
+/// Endereço da vtable de um `&dyn Display`.
+///
+/// O caminho oficial seria `std::ptr::metadata` (`DynMetadata`), mas ele
+/// ainda é instável (`#![feature(ptr_metadata)]`, só no nightly). Em stable
+/// lemos as duas palavras do ponteiro gordo e ficamos com a que não é o
+/// endereço dos dados — assim não dependemos da ordem dos campos.
+pub fn vtable_of(value: &dyn Display) -> usize {
+    let fat = value as *const dyn Display;
+    let data = fat as *const () as usize;
+    // SAFETY: `*const dyn Trait` tem exatamente duas palavras (dados +
+    // vtable), então o transmute para `[usize; 2]` tem o mesmo tamanho; só
+    // lemos os números, nunca os usamos como ponteiro.
+    let words: [usize; 2] = unsafe { mem::transmute(fat) };
+    if words[0] == data {
+        words[1]
+    } else {
+        words[0]
+    }
+}
+
+/// Instâncias do mesmo tipo concreto compartilham a vtable; tipos diferentes
+/// têm vtables diferentes.
+pub fn demo_vtable(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Vtables compartilhadas (Box<dyn Display>)")?;
+    let first: Box<dyn Display> = Box::new(7i32);
+    let second: Box<dyn Display> = Box::new(42i32);
+    let other: Box<dyn Display> = Box::new("texto");
+    for (label, value) in [
+        ("Box::new(7i32)", &first),
+        ("Box::new(42i32)", &second),
+        ("Box::new(\"texto\")", &other),
+    ] {
+        writeln!(
+            out,
+            "{:<18}: dados = {}, vtable = {}",
+            label,
+            pointer_address(&**value as *const dyn Display as *const ()),
+            hex_address(vtable_of(&**value))
+        )?;
+    }
+    writeln!(
+        out,
+        "mesma vtable para os dois i32: {}; i32 vs &str: {}",
+        vtable_of(&*first) == vtable_of(&*second),
+        vtable_of(&*first) == vtable_of(&*other)
+    )?;
+    writeln!(
+        out,
+        "(o compilador não garante uma vtable única por tipo entre crates/unidades de código, mas aqui elas coincidem)"
+    )
+}
+
+/// `Vec<u8>` aceita qualquer byte; `String` só existe com UTF-8 válido.
+/// Estragamos um byte do nome e vemos as duas formas de converter de volta.
+pub fn demo_utf8(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "String::from_utf8 e bytes inválidos")?;
+    let mut bytes = if name.is_empty() {
+        b"nome".to_vec()
+    } else {
+        name.as_bytes().to_vec()
+    };
+    let bad = bytes.len() / 2;
+    // 0xFF nunca aparece em UTF-8 válido
+    bytes[bad] = 0xff;
+    writeln!(
+        out,
+        "bytes com o índice {} trocado por 0xff: {:02x?}",
+        bad, bytes
+    )?;
+
+    match String::from_utf8(bytes.clone()) {
+        Ok(s) => writeln!(out, "from_utf8 aceitou (inesperado): {:?}", s)?,
+        Err(e) => writeln!(
+            out,
+            "String::from_utf8 -> Err: {} (valid_up_to = {})",
+            e.utf8_error(),
+            e.utf8_error().valid_up_to()
+        )?,
+    }
+    // from_utf8_lossy devolve Cow; into_owned garante um String no heap
+    let lossy: String = String::from_utf8_lossy(&bytes).into_owned();
+    writeln!(
+        out,
+        "String::from_utf8_lossy -> {:?} (U+FFFD no lugar do byte), buffer novo em {}, len = {}",
+        lossy,
+        pointer_address(lossy.as_ptr()),
+        lossy.len()
+    )?;
+    writeln!(
+        out,
+        "(o caractere de substituição ocupa 3 bytes, por isso o len pode crescer)"
+    )
+}
+
+/// `Box<[T]>` é um `Vec<T>` sem a capacidade: o handle tem duas palavras
+/// (ptr, len) em vez de três.
+pub fn demo_boxed_slice(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Box<[i32]> vs Vec<i32>")?;
+    let mut values: Vec<i32> = Vec::with_capacity(4);
+    values.extend([1, 2, 3, 4]);
+    let before = values.as_ptr();
+    writeln!(
+        out,
+        "Vec<i32>   : size_of_val(handle) = {:>2} (ptr, len, cap), len = {}, capacity = {}, buffer = {}",
+        mem::size_of_val(&values),
+        values.len(),
+        values.capacity(),
+        pointer_address(before)
+    )?;
+    let boxed: Box<[i32]> = values.into_boxed_slice();
+    writeln!(
+        out,
+        "Box<[i32]> : size_of_val(handle) = {:>2} (ptr, len),      len = {},               buffer = {}",
+        mem::size_of_val(&boxed),
+        boxed.len(),
+        pointer_address(boxed.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "mesmo buffer após into_boxed_slice: {} (capacity == len, nada a encolher)",
+        boxed.as_ptr() == before
+    )?;
+    writeln!(
+        out,
+        "(com capacity > len, into_boxed_slice encolhe o buffer antes — e ele pode mudar de lugar)"
+    )
+}
+
+/// `Rc::clone` só incrementa um contador; `Vec::clone` copia o buffer
+/// inteiro para uma alocação nova.
+pub fn demo_clone_cost(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Custo de clone: Rc<Vec<u8>> vs Vec<u8>")?;
+    const SIZE: usize = 1 << 20;
+    let data = vec![7u8; SIZE];
+    let shared = Rc::new(data.clone());
+
+    let start = Instant::now();
+    let rc_clone = Rc::clone(&shared);
+    let rc_time = start.elapsed();
+
+    let start = Instant::now();
+    let deep_clone = data.clone();
+    let deep_time = start.elapsed();
+
+    writeln!(
+        out,
+        "Rc::clone  ({} KiB): {:>10?}, buffer = {} (o mesmo do original: {}), strong_count = {}",
+        SIZE / 1024,
+        rc_time,
+        pointer_address(rc_clone.as_ptr()),
+        pointer_address(shared.as_ptr()),
+        Rc::strong_count(&shared)
+    )?;
+    writeln!(
+        out,
+        "Vec::clone ({} KiB): {:>10?}, buffer = {} (novo; o original está em {})",
+        SIZE / 1024,
+        deep_time,
+        pointer_address(deep_clone.as_ptr()),
+        pointer_address(data.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "(compartilhar com Rc custa o mesmo para 1 byte ou 1 GiB; o clone profundo cresce com o tamanho)"
+    )
+}
+
+/// Boxes de `u8`, `u32` e `f64`: o alocador devolve endereços múltiplos do
+/// `align_of` de cada tipo.
+pub fn demo_alignment(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Alinhamento: Box<u8>, Box<u32>, Box<f64>")?;
+    let byte = Box::new(1u8);
+    let word = Box::new(2u32);
+    let float = Box::new(3.0f64);
+    let rows = [
+        (
+            "Box<u8>",
+            &*byte as *const u8 as usize,
+            mem::align_of::<u8>(),
+        ),
+        (
+            "Box<u32>",
+            &*word as *const u32 as usize,
+            mem::align_of::<u32>(),
+        ),
+        (
+            "Box<f64>",
+            &*float as *const f64 as usize,
+            mem::align_of::<f64>(),
+        ),
+    ];
+    for (label, addr, align) in rows {
+        let ok = addr % align == 0;
+        writeln!(
+            out,
+            "{:<8}: pointee = {}, align_of = {}, addr % {} = {} {}",
+            label,
+            hex_address(addr),
+            align,
+            align,
+            addr % align,
+            if ok { "✓" } else { "✗" }
+        )?;
+    }
+    writeln!(
+        out,
+        "(garantia do alocador: todo ponteiro devolvido respeita o alinhamento do tipo)"
+    )
+}
+
+/// Distância entre frames consecutivos: dois níveis de recursão e uma
+/// chamada aninhada não recursiva, cada um medido a partir do frame de quem
+/// o chamou.
+pub fn demo_frame_gap(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Espaço entre frames da stack")?;
+    let local = 0u64;
+    let here = std::hint::black_box(&local) as *const u64 as usize;
+    let mut frames = Vec::new();
+    gap_recurse(2, ("demo_frame_gap", here), &mut frames);
+    gap_outer(("demo_frame_gap", here), &mut frames);
+    writeln!(
+        out,
+        "{:<14} {:>18} {:<16} {:>10}",
+        "frame", "local", "chamador", "distância"
+    )?;
+    writeln!(out, "{:<14} {:>18}", "demo_frame_gap", hex_address(here))?;
+    for frame in &frames {
+        writeln!(
+            out,
+            "{:<14} {:>18} {:<16} {:>10}",
+            frame.label,
+            hex_address(frame.addr),
+            frame.caller.0,
+            frame.caller.1.abs_diff(frame.addr)
+        )?;
+    }
+    writeln!(
+        out,
+        "(cada distância ≈ tamanho do frame + endereço de retorno e registradores salvos; \
+         no x86-64 System V folhas podem usar a red zone de 128 bytes abaixo do sp)"
+    )?;
+    writeln!(
+        out,
+        "(os números dependem do ABI, da plataforma e do nível de otimização)"
+    )
+}
+
+/// local de um frame de [`demo_frame_gap`] e o local do frame que o chamou
+struct GapFrame {
+    label: &'static str,
+    addr: usize,
+    caller: (&'static str, usize),
+}
+
+#[inline(never)]
+fn gap_recurse(remaining: usize, caller: (&'static str, usize), frames: &mut Vec<GapFrame>) {
+    if remaining == 0 {
+        return;
+    }
+    let local = remaining as u64;
+    let addr = std::hint::black_box(&local) as *const u64 as usize;
+    let label = if remaining == 2 {
+        "recursão 1"
+    } else {
+        "recursão 2"
+    };
+    frames.push(GapFrame {
+        label,
+        addr,
+        caller,
+    });
+    gap_recurse(remaining - 1, (label, addr), frames);
+}
+
+#[inline(never)]
+fn gap_outer(caller: (&'static str, usize), frames: &mut Vec<GapFrame>) {
+    let local = 1u64;
+    let addr = std::hint::black_box(&local) as *const u64 as usize;
+    frames.push(GapFrame {
+        label: "externa",
+        addr,
+        caller,
+    });
+    gap_inner(("externa", addr), frames);
+}
+
+#[inline(never)]
+fn gap_inner(caller: (&'static str, usize), frames: &mut Vec<GapFrame>) {
+    let local = 2u64;
+    frames.push(GapFrame {
+        label: "aninhada",
+        addr: std::hint::black_box(&local) as *const u64 as usize,
+        caller,
+    });
+}
+
+/// elementos do `Vec<Box<i32>>` de [`demo_nested_drop`]
+pub const NESTED_DROP_LEN: usize = 3;
+
+/// elemento de [`demo_nested_drop`]: o `Box` e um [`Tracer`] que anuncia o drop
+struct TracedBox {
+    value: Box<i32>,
+    _tracer: Tracer,
+}
+
+/// o `Vec` e, depois dele, um marcador: campos caem na ordem de declaração,
+/// então o marcador só é destruído depois que o `Vec` liberou tudo
+struct VecThenMarker {
+    items: Vec<TracedBox>,
+    _marker: Tracer,
+}
+
+/// Dropar um `Vec<Box<i32>>` roda o destrutor de cada elemento (liberando
+/// cada `Box`) antes de liberar o array de suporte.
+pub fn demo_nested_drop(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Drop aninhado: Vec<Box<i32>>")?;
+    const NAMES: [&str; NESTED_DROP_LEN] = ["elemento 0", "elemento 1", "elemento 2"];
+    let mut lines = Vec::new();
+    let drops = tracer::capture_drops(|| {
+        let holder = VecThenMarker {
+            items: NAMES
+                .iter()
+                .zip(1..)
+                .map(|(&name, value)| TracedBox {
+                    value: Box::new(value),
+                    _tracer: Tracer(name),
+                })
+                .collect(),
+            _marker: Tracer("Vec concluído (elementos e buffer liberados)"),
+        };
+        lines.push(format!(
+            "buffer do Vec (array de TracedBox) = {}",
+            pointer_address(holder.items.as_ptr())
+        ));
+        for (i, item) in holder.items.iter().enumerate() {
+            lines.push(format!(
+                "  [{}] Box -> {} (valor {})",
+                i,
+                pointer_address(&*item.value),
+                item.value
+            ));
+        }
+        // fim do escopo: holder cai aqui
+    });
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    writeln!(out, "fim do escopo:")?;
+    for name in drops {
+        writeln!(out, "drop: {}", name)?;
+    }
+    writeln!(
+        out,
+        "(cada elemento é destruído — e seu Box liberado — antes do array do Vec)"
+    )
+}
+
+/// `drain` no meio de um `Vec<i32>`: a alocação é a mesma (ponteiro e
+/// `capacity`), só o `len` encolhe e a cauda é deslocada dentro do buffer.
+pub fn demo_drain(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Vec::drain: mesmo buffer, cauda deslocada")?;
+    let mut values: Vec<i32> = vec![10, 20, 30, 40, 50, 60];
+    let print_state = |out: &mut dyn Write, when: &str, values: &Vec<i32>| -> io::Result<()> {
+        writeln!(
+            out,
+            "{:<6}: as_ptr() = {}, len = {}, capacity = {}",
+            when,
+            pointer_address(values.as_ptr()),
+            values.len(),
+            values.capacity()
+        )?;
+        for (i, addr) in element_addresses(values).iter().enumerate() {
+            writeln!(out, "  [{}] {} = {}", i, hex_address(*addr), values[i])?;
+        }
+        Ok(())
+    };
+    let (ptr_before, cap_before) = (values.as_ptr(), values.capacity());
+    print_state(out, "antes", &values)?;
+    let drained: Vec<i32> = values.drain(1..3).collect();
+    writeln!(out, "drain(1..3) removeu {:?}", drained)?;
+    print_state(out, "depois", &values)?;
+    writeln!(
+        out,
+        "mesmo ponteiro: {}, mesma capacity: {} (drain não realoca)",
+        values.as_ptr() == ptr_before,
+        values.capacity() == cap_before
+    )?;
+    writeln!(
+        out,
+        "(40, 50 e 60 foram copiados duas posições para trás no mesmo buffer)"
+    )
+}
+
+/// Como [`demo_realloc`], mas para `String`: cada `push` além da
+/// capacidade realoca o buffer UTF-8 e copia os bytes já escritos.
+pub fn demo_string_growth(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Crescimento de String")?;
+    let mut s = String::from("ab");
+    writeln!(
+        out,
+        "inicial: {:?}, len = {}, capacity = {}, buffer = {}",
+        s,
+        s.len(),
+        s.capacity(),
+        pointer_address(s.as_ptr())
+    )?;
+    let mut first_move = None;
+    for (i, c) in ('c'..='z').enumerate() {
+        let (old_cap, old_ptr) = (s.capacity(), s.as_ptr());
+        s.push(c);
+        if s.capacity() != old_cap {
+            let moved = s.as_ptr() != old_ptr;
+            if moved && first_move.is_none() {
+                first_move = Some(i + 1);
+            }
+            writeln!(
+                out,
+                "push #{:<2} ('{}'): capacity {:>2} -> {:>2}, buffer {} -> {} ({})",
+                i + 1,
+                c,
+                old_cap,
+                s.capacity(),
+                pointer_address(old_ptr),
+                pointer_address(s.as_ptr()),
+                if moved {
+                    "buffer moveu"
+                } else {
+                    "no mesmo lugar"
+                }
+            )?;
+        }
+    }
+    match first_move {
+        Some(n) => writeln!(
+            out,
+            "o buffer mudou de endereço pela primeira vez no push #{}",
+            n
+        )?,
+        None => writeln!(
+            out,
+            "o alocador estendeu o buffer no lugar em todas as realocações"
+        )?,
+    }
+    writeln!(
+        out,
+        "final: len = {}, capacity = {} (quando o buffer move, os bytes antigos são copiados para o novo)",
+        s.len(),
+        s.capacity()
+    )
+}
+
+/// elementos de [`demo_large_array`]: 4096 * 8 = 32 KiB, bem abaixo dos
+/// 2 MiB das threads criadas pela std (e dos 8 MiB típicos da principal no Linux)
+const LARGE_ARRAY_LEN: usize = 4096;
+
+/// `[u64; 4096]` na stack vs `Box<[u64; 4096]>`: no segundo caso a stack só
+/// guarda um ponteiro de 8 bytes; os 32 KiB ficam no heap.
+pub fn demo_large_array(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Array grande: [u64; 4096] na stack vs Box")?;
+    let on_stack = [0u64; LARGE_ARRAY_LEN];
+    let boxed = Box::new([0u64; LARGE_ARRAY_LEN]);
+    let on_stack = std::hint::black_box(&on_stack);
+    writeln!(
+        out,
+        "[u64; {}]      : endereço = {} (stack), size_of_val = {} bytes",
+        LARGE_ARRAY_LEN,
+        pointer_address(on_stack),
+        mem::size_of_val(on_stack)
+    )?;
+    writeln!(
+        out,
+        "Box<[u64; {}]> : handle   = {} (stack), size_of_val = {} bytes",
+        LARGE_ARRAY_LEN,
+        pointer_address(&boxed),
+        mem::size_of_val(&boxed)
+    )?;
+    writeln!(
+        out,
+        "                   dados    = {} (heap),  size_of_val = {} bytes",
+        pointer_address(&*boxed),
+        mem::size_of_val(&*boxed)
+    )?;
+    writeln!(
+        out,
+        "(arrays grandes na stack arriscam overflow — a thread principal costuma ter só alguns MiB; \
+         em Box só o ponteiro fica no frame)"
+    )?;
+    writeln!(
+        out,
+        "(sem otimização, Box::new([0; N]) pode montar o array na stack antes de copiá-lo)"
+    )
+}
+
+/// `(name.as_ptr(), name.as_str().as_ptr())` de uma `String` com o conteúdo
+/// `name`; ver [`demo_as_str`]
+pub fn as_str_pointers(name: &str) -> (usize, usize) {
+    let owned = name.to_string();
+    let view: &str = owned.as_str();
+    (owned.as_ptr() as usize, view.as_ptr() as usize)
+}
+
+/// `as_str()` não copia nem aloca: o `&str` aponta para o mesmo buffer da
+/// `String`, no deslocamento 0.
+pub fn demo_as_str(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "String::as_str(): a mesma memória, sem cópia")?;
+    let (owned, view) = as_str_pointers(name);
+    writeln!(out, "name.as_ptr()          = {}", hex_address(owned))?;
+    writeln!(out, "name.as_str().as_ptr() = {}", hex_address(view))?;
+    writeln!(out, "iguais: {}", if owned == view { "sim" } else { "não" })?;
+    writeln!(
+        out,
+        "(o &str é só (ponteiro, len) emprestado da String; nenhum byte é copiado)"
+    )
+}
+
+/// Mover um `Box` para dentro de um `Vec<Box<i32>>` copia só o ponteiro (para
+/// o buffer do `Vec`, no heap); o `i32` apontado não sai do lugar.
+pub fn demo_box_into_vec(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Box movido para dentro de um Vec")?;
+    let boxed = Box::new(42i32);
+    let pointee_before = &*boxed as *const i32;
+    writeln!(
+        out,
+        "antes : Box (ponteiro) em {} (stack), i32 em {} (heap)",
+        pointer_address(&boxed),
+        pointer_address(pointee_before)
+    )?;
+    // o move acontece aqui: o Box sai da variável local para o buffer
+    let boxes: Vec<Box<i32>> = vec![boxed];
+    let slot = &boxes[0];
+    writeln!(
+        out,
+        "depois: Box (ponteiro) em {} (buffer do Vec, heap), i32 em {} (heap)",
+        pointer_address(slot),
+        pointer_address(&**slot)
+    )?;
+    writeln!(
+        out,
+        "o i32 ficou no mesmo endereço: {}; o ponteiro agora vive em as_ptr() = {}: {}",
+        ptr::eq(&**slot, pointee_before),
+        pointer_address(boxes.as_ptr()),
+        ptr::eq(slot, boxes.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "(mover o Box move 8 bytes de ponteiro; o valor apontado não é copiado)"
+    )
+}
+
+/// Cada item de stack como `base - 0x...`, com a base sendo um local do
+/// início de `main` ([`crate::stack::main_base`]): a stack cresce para
+/// baixo, então quanto maior o deslocamento, mais fundo está o frame.
+pub fn demo_stack_base(out: &mut dyn Write, items: &[(&'static str, usize)]) -> io::Result<()> {
+    section(out, "Profundidade na stack a partir de main")?;
+    let Some(base) = crate::stack::main_base() else {
+        return writeln!(out, "(base de main não registrada neste processo)");
+    };
+    writeln!(
+        out,
+        "base (local no início de main) = {}",
+        hex_address(base)
+    )?;
+    let local = 0u8;
+    let here = std::hint::black_box(&local) as *const u8 as usize;
+    for &(label, addr) in items.iter().chain([("local desta demo", here)].iter()) {
+        let offset = if addr <= base {
+            format!("base - {:#x}", base - addr)
+        } else {
+            format!("base + {:#x}", addr - base)
+        };
+        writeln!(out, "{:<18} = {}  ({})", label, hex_address(addr), offset)?;
+    }
+    writeln!(
+        out,
+        "(deslocamentos negativos: a stack cresce para baixo a partir de main)"
+    )
+}
+
+/// Com capacidade reservada, `push` não realoca: os elementos já existentes
+/// continuam nos mesmos endereços.
+///
+/// O borrow checker não deixa guardar `&v[0]` durante um `push` (ele não
+/// sabe se haverá realocação), então guardamos os endereços e pegamos as
+/// referências de novo depois.
+pub fn demo_stable_refs(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Referências estáveis com capacidade reservada")?;
+    let mut values: Vec<u32> = Vec::with_capacity(8);
+    values.extend([1, 2]);
+    let first = &values[0] as *const u32;
+    let second = &values[1] as *const u32;
+    writeln!(
+        out,
+        "capacity = {}: &values[0] = {}, &values[1] = {}",
+        values.capacity(),
+        pointer_address(first),
+        pointer_address(second)
+    )?;
+    // 6 pushes cabem nos 8 reservados; um nono realocaria o buffer e os
+    // endereços acima passariam a apontar para memória liberada
+    for v in 3..=8 {
+        values.push(v);
+    }
+    let (first_now, second_now) = (&values[0] as *const u32, &values[1] as *const u32);
+    writeln!(
+        out,
+        "após 6 pushes (len = {}, capacity = {}): &values[0] = {}, &values[1] = {}",
+        values.len(),
+        values.capacity(),
+        pointer_address(first_now),
+        pointer_address(second_now)
+    )?;
+    writeln!(
+        out,
+        "mesmos endereços: {} (nenhuma realocação)",
+        ptr::eq(first, first_now) && ptr::eq(second, second_now)
+    )?;
+    writeln!(
+        out,
+        "(passar da capacidade realoca e invalida referências antigas — por isso o borrow checker proíbe segurá-las)"
+    )
+}
+
+/// `let s = *boxed;` tira a `String` do `Box`: o handle (ptr, len, cap) vai
+/// para a stack e a alocação do `Box` é liberada, mas o buffer de texto não
+/// se move. Reencaixotar cria uma alocação nova só para o handle.
+pub fn demo_unbox(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Tirando a String do Box (*boxed) e reencaixotando")?;
+    let boxed: Box<String> = Box::new(String::from("memória"));
+    let box_alloc = &*boxed as *const String;
+    let buffer = boxed.as_ptr();
+    writeln!(
+        out,
+        "Box<String>: handle da String em {} (alocação do Box, heap); texto em {} (heap)",
+        pointer_address(box_alloc),
+        pointer_address(buffer)
+    )?;
+
+    let s: String = *boxed;
+    writeln!(
+        out,
+        "let s = *boxed: handle agora em {} (stack); texto em {}",
+        pointer_address(&s),
+        pointer_address(s.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "buffer do texto inalterado: {} (só os 3 words do handle foram copiados; a alocação do Box foi liberada)",
+        ptr::eq(s.as_ptr(), buffer)
+    )?;
+
+    let reboxed: Box<String> = Box::new(s);
+    writeln!(
+        out,
+        "Box::new(s): handle em {} (nova alocação do Box); texto ainda em {}",
+        pointer_address(&*reboxed),
+        pointer_address(reboxed.as_ptr())
+    )?;
+    writeln!(
+        out,
+        "(o novo Box pode até reaproveitar o endereço liberado — quem decide é o alocador)"
+    )
+}
+
+/// tamanho do arquivo lido byte a byte em [`demo_io_buffering`]
+const IO_FILE_SIZE: usize = 64 * 1024;
+
+/// conta os bytes lendo um de cada vez de `reader`
+fn count_bytes_one_by_one(reader: &mut dyn io::Read) -> io::Result<usize> {
+    let mut byte = [0u8; 1];
+    let mut total = 0;
+    while reader.read(&mut byte)? == 1 {
+        total += 1;
+    }
+    Ok(total)
+}
+
+/// Ler byte a byte direto do `File` faz uma syscall por byte; o `BufReader`
+/// lê blocos para um buffer no heap e entrega os bytes a partir dele.
+pub fn demo_io_buffering(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "E/S: leitura sem buffer vs BufReader")?;
+    let path = env::temp_dir().join(format!("memoria_io_{}.bin", process::id()));
+    std::fs::write(&path, vec![b'x'; IO_FILE_SIZE])?;
+    let result = (|| -> io::Result<usize> {
+        let start = Instant::now();
+        let unbuffered = count_bytes_one_by_one(&mut std::fs::File::open(&path)?)?;
+        let unbuffered_time = start.elapsed();
+
+        let mut reader = io::BufReader::new(std::fs::File::open(&path)?);
+        let capacity = reader.capacity();
+        let start = Instant::now();
+        let buffered = count_bytes_one_by_one(&mut reader)?;
+        let buffered_time = start.elapsed();
+
+        writeln!(
+            out,
+            "File direto : {} bytes em {:?} (uma chamada ao sistema por byte)",
+            unbuffered, unbuffered_time
+        )?;
+        writeln!(
+            out,
+            "BufReader   : {} bytes em {:?} (buffer interno de {} bytes no heap)",
+            buffered, buffered_time, capacity
+        )?;
+        let ratio = unbuffered_time.as_secs_f64() / buffered_time.as_secs_f64().max(1e-9);
+        writeln!(out, "BufReader foi ~{:.0}x mais rápido", ratio)?;
+        Ok(capacity)
+    })();
+    // o arquivo temporário sai mesmo se a leitura falhou
+    let _ = std::fs::remove_file(&path);
+    let capacity = result?;
+    writeln!(
+        out,
+        "(o buffer troca {} syscalls por ~{}: memória a mais para muito menos trabalho do kernel)",
+        IO_FILE_SIZE,
+        IO_FILE_SIZE.div_ceil(capacity)
+    )
+}
+
+/// Um `Box<Tracer>` solta o valor apontado (e libera a alocação no heap) no
+/// momento em que o próprio `Box` sai de escopo, na mesma ordem LIFO dos
+/// locais da stack.
+pub fn demo_box_drop_timing(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Quando o Box solta o valor apontado")?;
+    let drops = box_drop_order();
+    writeln!(out, "declarados: a (stack), b (Box, heap), c (stack)")?;
+    for name in drops {
+        writeln!(out, "drop: {}", name)?;
+    }
+    writeln!(
+        out,
+        "(o Box é só mais um local: cai entre c e a, e leva junto o valor do heap)"
+    )
+}
+
+/// Ordem dos drops de `a` (stack), `b` (`Box`, heap) e `c` (stack) num
+/// mesmo escopo; ver [`demo_box_drop_timing`].
+pub fn box_drop_order() -> Vec<&'static str> {
+    tracer::capture_drops(|| {
+        let _a = Tracer("a (stack)");
+        let _b = Box::new(Tracer("b (Box, heap)"));
+        let _c = Tracer("c (stack)");
+    })
+}
+
+/// Distância, em elementos, de `items[from]` até `items[to]` via
+/// `ptr::offset_from`; os dois ponteiros vêm da mesma fatia (mesma alocação).
+pub fn element_distance<T>(items: &[T], from: usize, to: usize) -> isize {
+    let from: *const T = &items[from];
+    let to: *const T = &items[to];
+    // SAFETY: os dois ponteiros foram derivados da mesma fatia, então estão
+    // dentro da mesma alocação e a distância é múltiplo de `size_of::<T>()`.
+    unsafe { to.offset_from(from) }
+}
+
+/// `offset_from` mede a distância entre dois ponteiros *da mesma alocação*.
+/// Entre alocações diferentes o resultado é UB (os ponteiros não têm a mesma
+/// proveniência), por isso a demo nem tenta.
+pub fn demo_provenance(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Proveniência: offset_from dentro de uma alocação")?;
+    let bytes: Vec<u8> = (0..16).collect();
+    let (from, to) = (3, 11);
+    writeln!(
+        out,
+        "&bytes[{}] = {}, &bytes[{}] = {}",
+        from,
+        pointer_address(&bytes[from]),
+        to,
+        pointer_address(&bytes[to])
+    )?;
+    writeln!(
+        out,
+        "&bytes[{}].offset_from(&bytes[{}]) = {} elementos",
+        to,
+        from,
+        element_distance(&bytes, from, to)
+    )?;
+    writeln!(
+        out,
+        "(entre dois Vec diferentes não há distância definida: cada ponteiro só \
+         \"sabe\" andar dentro da própria alocação, e o compilador pode assumir isso)"
+    )
+}
+
+/// As três words de um `Vec<u8>` (ptr, len, capacity) moram no handle na
+/// stack; o buffer de `capacity` bytes mora no heap. Um push que estoura a
+/// capacidade troca ptr e capacity de uma vez.
+pub fn demo_vec_internals(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "As três words de um Vec<u8>")?;
+    let mut bytes: Vec<u8> = Vec::with_capacity(32);
+    bytes.extend_from_slice(&[b'x'; 32]);
+    // vizinho logo depois no heap: dificulta o realloc crescer no lugar
+    let neighbor = Box::new([0u8; 32]);
+    let words = |out: &mut dyn Write, bytes: &Vec<u8>, when: &str| {
+        writeln!(
+            out,
+            "{:<14} ptr = {}, len = {}, capacity = {}",
+            when,
+            hex_address(bytes.as_ptr() as usize),
+            bytes.len(),
+            bytes.capacity()
+        )
+    };
+    writeln!(
+        out,
+        "handle em {} (stack, {} bytes = 3 words de {})",
+        pointer_address(&bytes),
+        mem::size_of::<Vec<u8>>(),
+        mem::size_of::<usize>()
+    )?;
+    words(out, &bytes, "cheio:")?;
+    let before = bytes.as_ptr();
+    bytes.push(b'y');
+    words(out, &bytes, "após push(y):")?;
+    drop(neighbor);
+    let moved = if ptr::eq(before, bytes.as_ptr()) {
+        "o realloc conseguiu crescer no lugar, então ptr ficou igual"
+    } else {
+        "o buffer foi copiado para outro lugar do heap, então ptr mudou"
+    };
+    writeln!(
+        out,
+        "(len só conta elementos; capacity cresceu e {}; o handle continua em {})",
+        moved,
+        pointer_address(&bytes)
+    )
+}
+
+/// `(as_ptr, capacity)` de uma `String` com `text` e `reserved` bytes
+/// reservados: original, depois de movida e o clone; ver
+/// [`demo_capacity_on_move`]
+pub fn capacity_on_move(text: &str, reserved: usize) -> [(usize, usize); 3] {
+    let state = |s: &String| (s.as_ptr() as usize, s.capacity());
+    let mut original = String::with_capacity(reserved);
+    original.push_str(text);
+    let before = state(&original);
+    let moved = original;
+    let cloned = moved.clone();
+    [before, state(&moved), state(&cloned)]
+}
+
+/// Mover uma `String` copia só o handle: mesmo buffer, mesma capacidade.
+/// Clonar aloca um buffer novo do tamanho exato do conteúdo
+/// (`capacity == len`), sem a folga do original.
+pub fn demo_capacity_on_move(out: &mut dyn Write, name: &str) -> io::Result<()> {
+    section(out, "Capacidade: move preserva, clone ajusta ao len")?;
+    let reserved = name.len() + 64;
+    let steps = capacity_on_move(name, reserved);
+    let labels = ["String::with_capacity", "depois do move", "clone()"];
+    for (label, (ptr, capacity)) in labels.iter().zip(steps) {
+        writeln!(
+            out,
+            "{:<22} buffer = {}, len = {}, capacity = {}",
+            label,
+            hex_address(ptr),
+            name.len(),
+            capacity
+        )?;
+    }
+    writeln!(
+        out,
+        "(move é grátis e leva a folga junto; clone é uma alocação nova com \
+         exatamente len bytes)"
+    )
+}
+
+/// Os bits de um `Option<&i32>` como estão na memória, lidos como `usize`.
+pub fn option_ref_bits(opt: Option<&i32>) -> usize {
+    assert_eq!(mem::size_of::<Option<&i32>>(), mem::size_of::<usize>());
+    // SAFETY: os tamanhos batem (conferido acima) e a linguagem garante a
+    // otimização de ponteiro nulo para `Option<&T>`: `Some(r)` tem os bits
+    // do ponteiro e `None` é zero; todo padrão de bits é um `usize` válido.
+    unsafe { mem::transmute_copy::<Option<&i32>, usize>(&opt) }
+}
+
+/// Referências nunca são nulas, então `Option<&T>` usa o zero para `None` e
+/// cabe numa word: `Some(&x)` é literalmente o endereço de `x`.
+pub fn demo_option_ref(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Option<&i32>: None é o ponteiro nulo")?;
+    let x: i32 = 42;
+    writeln!(
+        out,
+        "size_of::<&i32>() = {}, size_of::<Option<&i32>>() = {}",
+        mem::size_of::<&i32>(),
+        mem::size_of::<Option<&i32>>()
+    )?;
+    writeln!(
+        out,
+        "&x                  = {}",
+        hex_address(&x as *const i32 as usize)
+    )?;
+    writeln!(
+        out,
+        "bits de Some(&x)    = {}",
+        hex_address(option_ref_bits(Some(&x)))
+    )?;
+    writeln!(
+        out,
+        "bits de None        = {}",
+        hex_address(option_ref_bits(None))
+    )?;
+    writeln!(
+        out,
+        "(uma &i32 nunca vale 0, então o compilador reaproveita o 0 para None: \
+         sem tag, sem byte extra)"
+    )
+}
+
+/// Mesmos campos de [`ReprCExample`] com o layout padrão (`repr(Rust)`): o
+/// compilador pode reordená-los.
+pub struct RustReprExample {
+    pub a: u8,
+    pub b: u32,
+    pub c: u8,
+}
+
+/// Tupla `(u8, u32, u8)` vs struct equivalente, ambas com o layout padrão.
+///
+/// Nenhuma das duas tem ordem garantida: o layout `repr(Rust)` não é
+/// especificado e o compilador costuma reordenar os campos para reduzir o
+/// padding. A demo só relata o que esta versão do compilador escolheu.
+pub fn demo_tuple_vs_struct(out: &mut dyn Write) -> io::Result<()> {
+    section(out, "Layout: tupla (u8, u32, u8) vs struct")?;
+    type Tuple = (u8, u32, u8);
+    let tuple_fields = [
+        ('0', mem::offset_of!(Tuple, 0), 1),
+        ('1', mem::offset_of!(Tuple, 1), 4),
+        ('2', mem::offset_of!(Tuple, 2), 1),
+    ];
+    let struct_fields = [
+        ('a', mem::offset_of!(RustReprExample, a), 1),
+        ('b', mem::offset_of!(RustReprExample, b), 4),
+        ('c', mem::offset_of!(RustReprExample, c), 1),
+    ];
+    for (label, fields, size) in [
+        ("(u8, u32, u8)", &tuple_fields, mem::size_of::<Tuple>()),
+        (
+            "struct {a, b, c}",
+            &struct_fields,
+            mem::size_of::<RustReprExample>(),
+        ),
+    ] {
+        let in_order = fields.windows(2).all(|w| w[0].1 < w[1].1);
+        writeln!(
+            out,
+            "{:<16} offsets {}={} {}={} {}={} | size_of = {:>2} | {}",
+            label,
+            fields[0].0,
+            fields[0].1,
+            fields[1].0,
+            fields[1].1,
+            fields[2].0,
+            fields[2].1,
+            size,
+            if in_order {
+                "ordem de declaração"
+            } else {
+                "reordenado pelo compilador"
+            }
+        )?;
+        writeln!(out, "{:<16} bytes: {}", "", byte_map(size, fields))?;
+    }
+    writeln!(
+        out,
+        "repr(C) com os mesmos campos: size_of = {} (ordem fixa, padding onde cair)",
+        mem::size_of::<ReprCExample>()
+    )?;
+    writeln!(
+        out,
+        "(o layout padrão não é especificado: tuplas e structs podem mudar entre versões \
+         do compilador; use repr(C) quando a ordem importa)"
+    )
+}
@@ -0,0 +1,73 @@
+//! Diagnósticos (feature `logging`).
+//!
+//! Com a feature, [`diag_debug!`], [`diag_info!`] e [`diag_warn!`] viram as
+//! macros do crate `log`, e o binário instala um logger simples no stderr
+//! (nível em `MEMORIA_LOG`: `error`, `warn`, `info`, `debug`, `trace`;
+//! padrão `warn`). Sem a feature, debug/info não geram código e os avisos
+//! continuam indo direto para o stderr.
+//!
+//! A saída educativa nunca passa por aqui: ela vai para o `Write` de sempre.
+
+#[cfg(feature = "logging")]
+pub use log;
+
+#[macro_export]
+macro_rules! diag_debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        $crate::diag::log::debug!($($arg)*);
+    }};
+}
+
+#[macro_export]
+macro_rules! diag_info {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        $crate::diag::log::info!($($arg)*);
+    }};
+}
+
+#[macro_export]
+macro_rules! diag_warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        $crate::diag::log::warn!($($arg)*);
+        #[cfg(not(feature = "logging"))]
+        eprintln!("aviso: {}", format_args!($($arg)*));
+    }};
+}
+
+/// Logger mínimo: `[NÍVEL] mensagem` no stderr.
+#[cfg(feature = "logging")]
+struct StderrLogger;
+
+#[cfg(feature = "logging")]
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Instala o logger do stderr com o nível de `MEMORIA_LOG` (sem a feature,
+/// não faz nada). Quem embute a biblioteca pode instalar o próprio logger.
+pub fn init() {
+    #[cfg(feature = "logging")]
+    {
+        static LOGGER: StderrLogger = StderrLogger;
+        let level = std::env::var("MEMORIA_LOG")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(log::LevelFilter::Warn);
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(level);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Comparação de dois relatórios salvos com `--json` (subcomando `diff`).
+//!
+//! Os endereços absolutos mudam entre execuções (ASLR), mas cada segmento
+//! costuma mudar "em bloco": se todas as entradas de um segmento se moveram
+//! pelo mesmo delta, os deslocamentos relativos entre elas foram preservados.
+
+use crate::json::hex;
+use crate::region::MemoryRegion;
+use serde_json::{json, Value};
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Endereços comparados, na ordem do esquema JSON, com a região de cada um.
+pub const ENTRIES: [(&str, MemoryRegion); 9] = [
+    ("welcome", MemoryRegion::Rodata),
+    ("name", MemoryRegion::Stack),
+    ("name_buffer", MemoryRegion::Heap),
+    ("stack_value", MemoryRegion::Stack),
+    ("heap_box", MemoryRegion::Stack),
+    ("heap_box_pointee", MemoryRegion::Heap),
+    ("name_chars", MemoryRegion::Stack),
+    ("name_chars_buffer", MemoryRegion::Heap),
+    ("example_function", MemoryRegion::Text),
+];
+
+/// Um endereço nas duas execuções.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub label: &'static str,
+    pub region: MemoryRegion,
+    pub before: usize,
+    pub after: usize,
+}
+
+impl DiffEntry {
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+
+    /// `after - before`, com sinal
+    pub fn delta(&self) -> i128 {
+        self.after as i128 - self.before as i128
+    }
+}
+
+/// Segmentos que o carregador move em bloco: stack, heap e a imagem do
+/// binário (`.rodata` e `.text` se movem juntos).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment {
+    Stack,
+    Heap,
+    Image,
+}
+
+impl Segment {
+    pub fn of(region: MemoryRegion) -> Option<Self> {
+        match region {
+            MemoryRegion::Stack => Some(Segment::Stack),
+            MemoryRegion::Heap => Some(Segment::Heap),
+            MemoryRegion::Rodata | MemoryRegion::Text => Some(Segment::Image),
+            MemoryRegion::Unknown => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Segment::Stack => "stack",
+            Segment::Heap => "heap",
+            Segment::Image => "binário (.rodata + .text)",
+        }
+    }
+}
+
+const SEGMENTS: [Segment; 3] = [Segment::Stack, Segment::Heap, Segment::Image];
+
+/// Resultado de [`diff_reports`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportDiff {
+    pub entries: Vec<DiffEntry>,
+}
+
+impl ReportDiff {
+    /// `true` se todas as entradas do segmento se moveram pelo mesmo delta
+    pub fn offsets_preserved(&self, segment: Segment) -> bool {
+        let mut deltas = self
+            .entries
+            .iter()
+            .filter(|e| Segment::of(e.region) == Some(segment))
+            .map(DiffEntry::delta);
+        match deltas.next() {
+            Some(first) => deltas.all(|d| d == first),
+            None => true,
+        }
+    }
+
+    /// menor endereço do segmento em cada execução (antes, depois)
+    pub fn segment_base(&self, segment: Segment) -> Option<(usize, usize)> {
+        let entries = || {
+            self.entries
+                .iter()
+                .filter(move |e| Segment::of(e.region) == Some(segment))
+        };
+        Some((
+            entries().map(|e| e.before).min()?,
+            entries().map(|e| e.after).min()?,
+        ))
+    }
+}
+
+/// lê `addresses.<label>` (string `0x...`) de um documento de `--json`
+fn address(doc: &Value, label: &str) -> Result<usize, String> {
+    let text = doc
+        .get("addresses")
+        .and_then(|a| a.get(label))
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("campo addresses.{} ausente", label))?;
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    usize::from_str_radix(digits, 16)
+        .map_err(|_| format!("addresses.{} inválido: '{}'", label, text))
+}
+
+/// Compara dois documentos gerados por [`crate::json::report_to_json`].
+pub fn diff_reports(before: &Value, after: &Value) -> Result<ReportDiff, String> {
+    let entries = ENTRIES
+        .iter()
+        .map(|&(label, region)| {
+            Ok(DiffEntry {
+                label,
+                region,
+                before: address(before, label)?,
+                after: address(after, label)?,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(ReportDiff { entries })
+}
+
+/// Tabela legível: um endereço por linha e, no fim, um veredito por segmento.
+pub fn write_diff(out: &mut dyn Write, diff: &ReportDiff) -> io::Result<()> {
+    writeln!(
+        out,
+        "{:<18} {:<7} {:>18} {:>18} {:>14}  estado",
+        "label", "região", "antes", "depois", "delta"
+    )?;
+    for e in &diff.entries {
+        writeln!(
+            out,
+            "{:<18} {:<7} {:>18} {:>18} {:>+14}  {}",
+            e.label,
+            e.region.label(),
+            hex(e.before),
+            hex(e.after),
+            e.delta(),
+            if e.changed() { "mudou" } else { "igual" }
+        )?;
+    }
+    writeln!(out)?;
+    for segment in SEGMENTS {
+        writeln!(
+            out,
+            "deslocamentos relativos no segmento {}: {}",
+            segment.label(),
+            if diff.offsets_preserved(segment) {
+                "preservados"
+            } else {
+                "alterados"
+            }
+        )?;
+    }
+    writeln!(
+        out,
+        "(ASLR move cada segmento em bloco; a estrutura interna tende a se manter)"
+    )
+}
+
+/// O mesmo conteúdo de [`write_diff`] como JSON.
+pub fn diff_to_json(diff: &ReportDiff) -> Value {
+    let entries: Vec<Value> = diff
+        .entries
+        .iter()
+        .map(|e| {
+            json!({
+                "label": e.label,
+                "region": e.region.label(),
+                "before": hex(e.before),
+                "after": hex(e.after),
+                "delta": e.delta().to_string(),
+                "changed": e.changed(),
+            })
+        })
+        .collect();
+    json!({
+        "entries": entries,
+        "offsets_preserved": {
+            "stack": diff.offsets_preserved(Segment::Stack),
+            "heap": diff.offsets_preserved(Segment::Heap),
+            "image": diff.offsets_preserved(Segment::Image),
+        },
+    })
+}
+
+/// lê e interpreta um arquivo de `--json`
+fn load(path: &str) -> io::Result<Value> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("'{}': {}", path, e)))?;
+    serde_json::from_str(&text).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("'{}': JSON inválido: {}", path, e),
+        )
+    })
+}
+
+/// Subcomando `diff A.json B.json` (com `--json`, o diff sai em JSON).
+pub fn run_diff(out: &mut dyn Write, before: &str, after: &str, as_json: bool) -> io::Result<()> {
+    let diff = diff_reports(&load(before)?, &load(after)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if as_json {
+        writeln!(out, "{}", diff_to_json(&diff))
+    } else {
+        write_diff(out, &diff)
+    }
+}
+
+/// Caminho do próprio executável: o `argv[0]` quando ele aponta para um
+/// arquivo (ex.: `./target/debug/memoria_demo`), senão
+/// [`env::current_exe`] (ex.: binário achado pelo `PATH`).
+fn own_executable() -> io::Result<PathBuf> {
+    match env::args_os().next().map(PathBuf::from) {
+        Some(path) if path.components().count() > 1 && path.is_file() => Ok(path),
+        _ => env::current_exe(),
+    }
+}
+
+/// roda o binário com `--json --relative` e devolve o documento
+fn launch_report(exe: &PathBuf) -> io::Result<Value> {
+    let child = Command::new(exe)
+        .args([
+            "--json",
+            "--relative",
+            "--name",
+            "aslr",
+            "--birth-year",
+            "2000",
+        ])
+        .stdin(Stdio::null())
+        .output()?;
+    if !child.status.success() {
+        return Err(io::Error::other(format!(
+            "lançamento de {} falhou ({})",
+            exe.display(),
+            child.status
+        )));
+    }
+    serde_json::from_slice(&child.stdout).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("JSON inválido do lançamento: {}", e),
+        )
+    })
+}
+
+/// Subcomando `compare-launches`: dois processos novos, dois relatórios.
+///
+/// ASLR só é visível de um processo para outro; aqui as bases absolutas de
+/// cada segmento são comparadas entre os lançamentos, e a estrutura interna
+/// (deslocamentos dentro do segmento) também.
+pub fn compare_launches(out: &mut dyn Write) -> io::Result<()> {
+    let exe = own_executable()?;
+    writeln!(out, "--- compare-launches: {} (x2) ---", exe.display())?;
+    let first = launch_report(&exe)?;
+    let second = launch_report(&exe)?;
+    let diff =
+        diff_reports(&first, &second).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut bases_moved = false;
+    let mut structure_kept = true;
+    for segment in SEGMENTS {
+        let Some((before, after)) = diff.segment_base(segment) else {
+            continue;
+        };
+        let preserved = diff.offsets_preserved(segment);
+        bases_moved |= before != after;
+        structure_kept &= preserved;
+        writeln!(
+            out,
+            "{:<26} base {} -> {} ({}), estrutura relativa {}",
+            segment.label(),
+            hex(before),
+            hex(after),
+            if before != after { "mudou" } else { "igual" },
+            if preserved { "idêntica" } else { "diferente" }
+        )?;
+    }
+    let verdict = match (bases_moved, structure_kept) {
+        (true, true) => "ASLR ativo: as bases mudaram, a estrutura dentro de cada segmento não",
+        (true, false) => "as bases mudaram e a estrutura também (alocador ou ambiente diferente?)",
+        (false, _) => "as bases não mudaram: ASLR provavelmente inativo",
+    };
+    writeln!(out, "{}", verdict)
+}
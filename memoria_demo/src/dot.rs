@@ -0,0 +1,87 @@
+//! Exportação do mapa de memória em Graphviz DOT (flag `--dot`).
+
+use crate::{render, AddressReport};
+use std::fmt::Write as _;
+
+/// nó do grafo: (id DOT, rótulo, endereço)
+type Node = (&'static str, &'static str, usize);
+
+/// Monta o grafo: stack, heap e dados estáticos em clusters separados, com
+/// arestas para cada ponteiro (handle -> buffer, `Box` -> valor).
+///
+/// Uso: `memoria_demo --dot ... | dot -Tpng -o memoria.png`
+pub fn report_to_dot(report: &AddressReport) -> String {
+    let clusters: [(&str, &str, &[Node]); 4] = [
+        (
+            "stack",
+            "stack",
+            &[
+                ("name", "name: String", report.name),
+                ("stack_value", "stack_value: i32", report.stack_value),
+                ("heap_box", "heap_box: Box<i32>", report.heap_box),
+                ("name_chars", "name_chars: Vec<char>", report.name_chars),
+            ],
+        ),
+        (
+            "heap",
+            "heap",
+            &[
+                ("name_buffer", "buffer do name", report.name_buffer),
+                (
+                    "heap_box_pointee",
+                    "*heap_box (i32)",
+                    report.heap_box_pointee,
+                ),
+                (
+                    "name_chars_buffer",
+                    "buffer do name_chars",
+                    report.name_chars_buffer,
+                ),
+            ],
+        ),
+        (
+            "rodata",
+            ".rodata",
+            &[("welcome", "welcome: &str", report.welcome)],
+        ),
+        (
+            "text",
+            ".text",
+            &[(
+                "example_function",
+                "example_function",
+                report.example_function,
+            )],
+        ),
+    ];
+    let edges = [
+        ("name", "name_buffer"),
+        ("heap_box", "heap_box_pointee"),
+        ("name_chars", "name_chars_buffer"),
+    ];
+
+    let mut dot = String::new();
+    // escrever em String não falha; os `let _` só descartam o Ok(())
+    let _ = writeln!(dot, "digraph memoria {{");
+    let _ = writeln!(dot, "    rankdir=LR;");
+    let _ = writeln!(dot, "    node [shape=box, fontname=\"monospace\"];");
+    for (id, label, nodes) in clusters {
+        let _ = writeln!(dot, "    subgraph cluster_{} {{", id);
+        let _ = writeln!(dot, "        label=\"{}\";", label);
+        for (node, text, addr) in nodes {
+            let _ = writeln!(
+                dot,
+                "        {} [label=\"{}\\n{}\"];",
+                node,
+                text,
+                render::hex_address(*addr)
+            );
+        }
+        let _ = writeln!(dot, "    }}");
+    }
+    for (from, to) in edges {
+        let _ = writeln!(dot, "    {} -> {};", from, to);
+    }
+    let _ = writeln!(dot, "}}");
+    dot
+}
@@ -0,0 +1,32 @@
+//! Códigos de saída do processo, para scripts e CI.
+//!
+//! | código | significado                                           |
+//! |--------|-------------------------------------------------------|
+//! | 0      | sucesso                                               |
+//! | 1      | erro de E/S inesperado (ex.: stdout fechado)          |
+//! | 2      | argumentos de linha de comando inválidos              |
+//! | 3      | arquivo de `--input` ilegível, incompleto ou inválido |
+//! |        | (ou arquivo de `--replay` ilegível)                   |
+//! | 4      | entrada encerrada (EOF) antes do nome/nascimento      |
+//! | 5      | alguma demo entrou em pânico (o resto rodou)          |
+//! | 6      | alguma verificação de `--self-check` falhou           |
+
+use std::process::ExitCode;
+
+/// Motivo de término do processo; ver a tabela no topo do módulo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    Io = 1,
+    BadArgs = 2,
+    BadInputFile = 3,
+    InputClosed = 4,
+    DemoFailed = 5,
+    SelfCheckFailed = 6,
+}
+
+impl From<Exit> for ExitCode {
+    fn from(exit: Exit) -> Self {
+        ExitCode::from(exit as u8)
+    }
+}
@@ -0,0 +1,37 @@
+//! Hexdump clássico: endereço, 16 bytes em hex e a coluna ASCII.
+
+use crate::render::hex_address;
+use std::io::{self, Write};
+
+const BYTES_PER_ROW: usize = 16;
+
+/// Escreve `bytes` em linhas de 16, rotulando cada linha com o endereço
+/// absoluto (`base_addr` + deslocamento). Bytes fora do ASCII imprimível
+/// (inclusive os de caracteres UTF-8 multibyte) aparecem como `.`.
+pub fn hexdump(bytes: &[u8], base_addr: usize, out: &mut dyn Write) -> io::Result<()> {
+    for (row, chunk) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+        write!(out, "{} ", hex_address(base_addr + row * BYTES_PER_ROW))?;
+        for i in 0..BYTES_PER_ROW {
+            // espaço extra no meio da linha, como no `hexdump -C`
+            if i == BYTES_PER_ROW / 2 {
+                write!(out, " ")?;
+            }
+            match chunk.get(i) {
+                Some(b) => write!(out, " {:02x}", b)?,
+                None => write!(out, "   ")?,
+            }
+        }
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        writeln!(out, "  |{}|", ascii)?;
+    }
+    Ok(())
+}
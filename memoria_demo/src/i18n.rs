@@ -0,0 +1,288 @@
+//! Textos exibidos ao usuário, por idioma (flag `--lang`).
+//!
+//! Todas as mensagens passam por [`msg`]; para adicionar um idioma basta
+//! uma variante nova em [`Lang`] e uma tabela com uma entrada por [`Msg`].
+//! Cada tabela é um `match` exaustivo: uma chave nova sem tradução em algum
+//! idioma não compila.
+
+/// Idioma da saída.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    Pt,
+    En,
+    Es,
+}
+
+impl Lang {
+    /// interpreta o código usado em `--lang` (`pt`, `en`, `es`)
+    pub fn from_code(code: &str) -> Option<Lang> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "pt" => Some(Lang::Pt),
+            "en" => Some(Lang::En),
+            "es" => Some(Lang::Es),
+            _ => None,
+        }
+    }
+}
+
+/// Chaves das mensagens traduzidas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    Banner,
+    Welcome,
+    PromptName,
+    PromptBirthYear,
+    InvalidYear,
+    InputClosed,
+    ResultHeader,
+    LabelName,
+    LabelBirthYear,
+    LabelCurrentYear,
+    LabelAge,
+    LabelExactAge,
+    YearsUnit,
+    AddressesHeader,
+    Inferred,
+    AddrWelcome,
+    AddrName,
+    AddrNameBuffer,
+    AddrStackValue,
+    AddrHeapBox,
+    AddrHeapBoxPointee,
+    AddrNameChars,
+    AddrNameCharsBuffer,
+    AddrExampleFunction,
+    SizesHeader,
+    SizesValueColumn,
+    FrameHeader,
+    AddrParam,
+    AddrLocal,
+    CallerLocal,
+    DeeperFrameLower,
+    DeeperFrameHigher,
+    StackGrowsDown,
+    StackGrowsUp,
+    Tip,
+    AllocTotal,
+    AllocLive,
+    MenuHeader,
+    MenuAll,
+    MenuPrompt,
+    MenuInvalid,
+    RepeatHeader,
+    RepeatIteration,
+    RepeatStable,
+    Yes,
+    No,
+    RelativeBase,
+    TotalsHeader,
+    TotalsStack,
+    TotalsHeap,
+    CallerNameAddr,
+    AddrParamSlot,
+    ParamIsCallerPointer,
+    ParamIsNotCallerPointer,
+    ParamCopiedNote,
+    DemoFailed,
+    DemosFailedSummary,
+    NameTruncated,
+}
+
+/// Texto de `key` no idioma `lang`.
+pub fn msg(key: Msg, lang: Lang) -> &'static str {
+    match lang {
+        Lang::Pt => pt(key),
+        Lang::En => en(key),
+        Lang::Es => es(key),
+    }
+}
+
+fn pt(key: Msg) -> &'static str {
+    match key {
+        Msg::Banner => "=== memória_demo (Stack vs Heap) ===",
+        Msg::Welcome => crate::WELCOME,
+        Msg::PromptName => "Nome do estudante: ",
+        Msg::PromptBirthYear => "Ano de nascimento (YYYY ou YYYY-MM-DD): ",
+        Msg::InvalidYear => "Ano inválido. Tente novamente.",
+        Msg::InputClosed => "entrada encerrada",
+        Msg::ResultHeader => "--- Resultado ---",
+        Msg::LabelName => "Nome (String)   : ",
+        Msg::LabelBirthYear => "Ano nascimento  : ",
+        Msg::LabelCurrentYear => "Ano atual       : ",
+        Msg::LabelAge => "Idade aproximada: ",
+        Msg::LabelExactAge => "Idade           : ",
+        Msg::YearsUnit => "anos",
+        Msg::AddressesHeader => "--- Endereços / Pistas de memória ---",
+        Msg::Inferred => "inferido",
+        Msg::AddrWelcome => "&welcome (literal .rodata)",
+        Msg::AddrName => "name (String object on stack)",
+        Msg::AddrNameBuffer => "name buffer (heap) as_ptr()",
+        Msg::AddrStackValue => "stack_value (stack)",
+        Msg::AddrHeapBox => "heap_box pointer (on stack)",
+        Msg::AddrHeapBoxPointee => "heap_box pointee (heap)",
+        Msg::AddrNameChars => "name_chars Vec struct (stack)",
+        Msg::AddrNameCharsBuffer => "name_chars buffer (heap)",
+        Msg::AddrExampleFunction => "example_function (endereço código)",
+        Msg::SizesHeader => "--- Tamanhos / alinhamentos ---",
+        Msg::SizesValueColumn => "valor",
+        Msg::FrameHeader => "--- Dentro de outra função (novo frame na stack) ---",
+        Msg::AddrParam => "param name (referência) addr",
+        Msg::AddrLocal => "local (i32) addr",
+        Msg::CallerLocal => "local do chamador",
+        Msg::DeeperFrameLower => "este frame (mais profundo) está em endereço menor",
+        Msg::DeeperFrameHigher => "este frame (mais profundo) está em endereço maior",
+        Msg::StackGrowsDown => "=> a stack cresce para baixo neste alvo.",
+        Msg::StackGrowsUp => "=> a stack cresce para cima neste alvo.",
+        Msg::Tip => "(Dica) Para inspecionar o binário/assembly: veja seção 'Ver binário / assembly' no README.",
+        Msg::AllocTotal => "bytes alocados no heap",
+        Msg::AllocLive => "vivos no fim",
+        Msg::MenuHeader => "--- Demonstrações disponíveis ---",
+        Msg::MenuAll => "todas (Enter)",
+        Msg::MenuPrompt => "Escolha uma demonstração (número ou 't' para todas): ",
+        Msg::MenuInvalid => "Opção inválida. Tente novamente.",
+        Msg::RepeatHeader => "--- Coletas repetidas no mesmo processo (--repeat) ---",
+        Msg::RepeatIteration => "iteração",
+        Msg::RepeatStable => "endereços estáveis entre iterações",
+        Msg::Yes => "sim",
+        Msg::No => "não",
+        Msg::RelativeBase => "base (menor endereço capturado)",
+        Msg::TotalsHeader => "--- Resumo: stack vs heap ---",
+        Msg::TotalsStack => "stack (locais capturados, soma dos size_of)",
+        Msg::TotalsHeap => "heap (capacidades + valor do Box)",
+        Msg::CallerNameAddr => "&name no chamador",
+        Msg::AddrParamSlot => "&name (slot do parâmetro)",
+        Msg::ParamIsCallerPointer => "=> o parâmetro recebeu exatamente o &name do chamador;",
+        Msg::ParamIsNotCallerPointer => "=> o parâmetro NÃO coincide com o &name do chamador;",
+        Msg::ParamCopiedNote => "o valor copiado é o ponteiro, guardado num endereço novo da stack.",
+        Msg::DemoFailed => "demo falhou:",
+        Msg::DemosFailedSummary => "demos com falha:",
+        Msg::NameTruncated => "nota: nome truncado (--max-name-len); caracteres mantidos:",
+    }
+}
+
+fn en(key: Msg) -> &'static str {
+    match key {
+        Msg::Banner => "=== memoria_demo (Stack vs Heap) ===",
+        Msg::Welcome => "Welcome to the memory demo!",
+        Msg::PromptName => "Student name: ",
+        Msg::PromptBirthYear => "Birth year (YYYY or YYYY-MM-DD): ",
+        Msg::InvalidYear => "Invalid year. Try again.",
+        Msg::InputClosed => "input closed",
+        Msg::ResultHeader => "--- Result ---",
+        Msg::LabelName => "Name (String)   : ",
+        Msg::LabelBirthYear => "Birth year      : ",
+        Msg::LabelCurrentYear => "Current year    : ",
+        Msg::LabelAge => "Approximate age : ",
+        Msg::LabelExactAge => "Age             : ",
+        Msg::YearsUnit => "years",
+        Msg::AddressesHeader => "--- Addresses / Memory hints ---",
+        Msg::Inferred => "inferred",
+        Msg::AddrWelcome => "&welcome (.rodata literal)",
+        Msg::AddrName => "name (String object on stack)",
+        Msg::AddrNameBuffer => "name buffer (heap) as_ptr()",
+        Msg::AddrStackValue => "stack_value (stack)",
+        Msg::AddrHeapBox => "heap_box pointer (on stack)",
+        Msg::AddrHeapBoxPointee => "heap_box pointee (heap)",
+        Msg::AddrNameChars => "name_chars Vec struct (stack)",
+        Msg::AddrNameCharsBuffer => "name_chars buffer (heap)",
+        Msg::AddrExampleFunction => "example_function (code address)",
+        Msg::SizesHeader => "--- Sizes / alignments ---",
+        Msg::SizesValueColumn => "value",
+        Msg::FrameHeader => "--- Inside another function (new stack frame) ---",
+        Msg::AddrParam => "param name (reference) addr",
+        Msg::AddrLocal => "local (i32) addr",
+        Msg::CallerLocal => "caller's local",
+        Msg::DeeperFrameLower => "this (deeper) frame is at a lower address",
+        Msg::DeeperFrameHigher => "this (deeper) frame is at a higher address",
+        Msg::StackGrowsDown => "=> the stack grows downward on this target.",
+        Msg::StackGrowsUp => "=> the stack grows upward on this target.",
+        Msg::Tip => "(Tip) To inspect the binary/assembly: see the 'Ver binário / assembly' section in the README.",
+        Msg::AllocTotal => "bytes allocated on the heap",
+        Msg::AllocLive => "live at the end",
+        Msg::MenuHeader => "--- Available demos ---",
+        Msg::MenuAll => "all (Enter)",
+        Msg::MenuPrompt => "Pick a demo (number or 'all'): ",
+        Msg::MenuInvalid => "Invalid option. Try again.",
+        Msg::RepeatHeader => "--- Repeated collections in one process (--repeat) ---",
+        Msg::RepeatIteration => "iteration",
+        Msg::RepeatStable => "addresses stable across iterations",
+        Msg::Yes => "yes",
+        Msg::No => "no",
+        Msg::RelativeBase => "base (lowest captured address)",
+        Msg::TotalsHeader => "--- Summary: stack vs heap ---",
+        Msg::TotalsStack => "stack (captured locals, sum of size_of)",
+        Msg::TotalsHeap => "heap (capacities + Box value)",
+        Msg::CallerNameAddr => "caller's &name",
+        Msg::AddrParamSlot => "&name (parameter slot)",
+        Msg::ParamIsCallerPointer => "=> the parameter received exactly the caller's &name;",
+        Msg::ParamIsNotCallerPointer => "=> the parameter does NOT match the caller's &name;",
+        Msg::ParamCopiedNote => "the value copied in is the pointer, stored at a new stack address.",
+        Msg::DemoFailed => "demo failed:",
+        Msg::DemosFailedSummary => "failed demos:",
+        Msg::NameTruncated => "note: name truncated (--max-name-len); characters kept:",
+    }
+}
+
+fn es(key: Msg) -> &'static str {
+    match key {
+        Msg::Banner => "=== memoria_demo (Stack vs Heap) ===",
+        Msg::Welcome => "¡Bienvenido a la demo de memoria!",
+        Msg::PromptName => "Nombre del estudiante: ",
+        Msg::PromptBirthYear => "Año de nacimiento (YYYY o YYYY-MM-DD): ",
+        Msg::InvalidYear => "Año inválido. Inténtalo de nuevo.",
+        Msg::InputClosed => "entrada cerrada",
+        Msg::ResultHeader => "--- Resultado ---",
+        Msg::LabelName => "Nombre (String) : ",
+        Msg::LabelBirthYear => "Año nacimiento  : ",
+        Msg::LabelCurrentYear => "Año actual      : ",
+        Msg::LabelAge => "Edad aproximada : ",
+        Msg::LabelExactAge => "Edad            : ",
+        Msg::YearsUnit => "años",
+        Msg::AddressesHeader => "--- Direcciones / Pistas de memoria ---",
+        Msg::Inferred => "inferido",
+        Msg::AddrWelcome => "&welcome (literal .rodata)",
+        Msg::AddrName => "name (objeto String en la stack)",
+        Msg::AddrNameBuffer => "buffer de name (heap) as_ptr()",
+        Msg::AddrStackValue => "stack_value (stack)",
+        Msg::AddrHeapBox => "puntero heap_box (en la stack)",
+        Msg::AddrHeapBoxPointee => "valor de heap_box (heap)",
+        Msg::AddrNameChars => "struct Vec name_chars (stack)",
+        Msg::AddrNameCharsBuffer => "buffer de name_chars (heap)",
+        Msg::AddrExampleFunction => "example_function (dirección código)",
+        Msg::SizesHeader => "--- Tamaños / alineaciones ---",
+        Msg::SizesValueColumn => "valor",
+        Msg::FrameHeader => "--- Dentro de otra función (nuevo frame en la stack) ---",
+        Msg::AddrParam => "param name (referencia) dir",
+        Msg::AddrLocal => "local (i32) dir",
+        Msg::CallerLocal => "local del llamador",
+        Msg::DeeperFrameLower => "este frame (más profundo) está en una dirección menor",
+        Msg::DeeperFrameHigher => "este frame (más profundo) está en una dirección mayor",
+        Msg::StackGrowsDown => "=> la stack crece hacia abajo en este objetivo.",
+        Msg::StackGrowsUp => "=> la stack crece hacia arriba en este objetivo.",
+        Msg::Tip => "(Consejo) Para inspeccionar el binario/assembly: ver la sección 'Ver binário / assembly' del README.",
+        Msg::AllocTotal => "bytes asignados en el heap",
+        Msg::AllocLive => "vivos al final",
+        Msg::MenuHeader => "--- Demostraciones disponibles ---",
+        Msg::MenuAll => "todas (Enter)",
+        Msg::MenuPrompt => "Elige una demostración (número o 't' para todas): ",
+        Msg::MenuInvalid => "Opción inválida. Inténtalo de nuevo.",
+        Msg::RepeatHeader => "--- Recolecciones repetidas en el mismo proceso (--repeat) ---",
+        Msg::RepeatIteration => "iteración",
+        Msg::RepeatStable => "direcciones estables entre iteraciones",
+        Msg::Yes => "sí",
+        Msg::No => "no",
+        Msg::RelativeBase => "base (menor dirección capturada)",
+        Msg::TotalsHeader => "--- Resumen: stack vs heap ---",
+        Msg::TotalsStack => "stack (locales capturados, suma de size_of)",
+        Msg::TotalsHeap => "heap (capacidades + valor del Box)",
+        Msg::CallerNameAddr => "&name en el llamador",
+        Msg::AddrParamSlot => "&name (slot del parámetro)",
+        Msg::ParamIsCallerPointer => "=> el parámetro recibió exactamente el &name del llamador;",
+        Msg::ParamIsNotCallerPointer => "=> el parámetro NO coincide con el &name del llamador;",
+        Msg::ParamCopiedNote => "el valor copiado es el puntero, guardado en una dirección nueva de la stack.",
+        Msg::DemoFailed => "la demo falló:",
+        Msg::DemosFailedSummary => "demos con fallos:",
+        Msg::NameTruncated => "nota: nombre truncado (--max-name-len); caracteres conservados:",
+    }
+}
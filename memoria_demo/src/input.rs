@@ -0,0 +1,131 @@
+//! Leitura de linhas da entrada, distinguindo linha vazia de fim da entrada.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+/// Resultado de [`read_line_from`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputResult {
+    /// uma linha (sem o `\n`/`\r\n` final; pode ser vazia)
+    Line(String),
+    /// fim da entrada antes de qualquer byte
+    Eof,
+}
+
+impl InputResult {
+    /// a linha, ou `ErrorKind::UnexpectedEof` — para quem trata o fim da
+    /// entrada como "encerrar" via `?`
+    pub fn into_line(self) -> io::Result<String> {
+        match self {
+            InputResult::Line(line) => Ok(line),
+            InputResult::Eof => Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+}
+
+/// Lê até um `\n` ou até o fim da entrada, repetindo leituras parciais e
+/// interrompidas (`ErrorKind::Interrupted`).
+///
+/// Tira só a quebra de linha final; espaços nas pontas ficam para quem
+/// interpreta o valor (`trim` no parsing). Bytes que não são UTF-8 válido
+/// (console do Windows, entrada binária por pipe) viram `U+FFFD` com um
+/// aviso, em vez de abortar a leitura.
+pub fn read_line_from(reader: &mut dyn BufRead) -> io::Result<InputResult> {
+    let mut bytes = Vec::new();
+    loop {
+        match reader.read_until(b'\n', &mut bytes) {
+            Ok(0) => break,
+            Ok(_) if bytes.ends_with(b"\n") => break,
+            Ok(_) => continue,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    if bytes.is_empty() {
+        return Ok(InputResult::Eof);
+    }
+    let mut line = match String::from_utf8(bytes) {
+        Ok(line) => line,
+        Err(e) => {
+            let line = String::from_utf8_lossy(e.as_bytes()).into_owned();
+            crate::diag_warn!(
+                "entrada com bytes que não são UTF-8 válido; substituídos por U+FFFD: {:?}",
+                line.trim_end()
+            );
+            line
+        }
+    };
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(InputResult::Line(line))
+}
+
+/// Primeiros `max_chars` caracteres de `text` (`--max-name-len`); o corte
+/// cai sempre numa fronteira de `char`, nunca no meio de um caractere UTF-8.
+pub fn truncate_chars(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// De onde vêm as respostas digitadas (nome, nascimento, menu): da entrada
+/// ao vivo ou de uma gravação (`--replay`), opcionalmente copiadas para um
+/// arquivo, uma por linha (`--record`).
+#[derive(Default)]
+pub struct Session {
+    replay: Option<VecDeque<String>>,
+    record: Option<Box<dyn Write>>,
+}
+
+impl Session {
+    /// respostas vêm da entrada ao vivo
+    pub fn live() -> Self {
+        Session::default()
+    }
+
+    /// respostas vêm de `recording` (uma por linha), na ordem
+    pub fn replaying(recording: &str) -> Self {
+        Session {
+            replay: Some(recording.lines().map(str::to_string).collect()),
+            record: None,
+        }
+    }
+
+    /// Também grava cada resposta em `sink`.
+    pub fn recording_to(mut self, sink: Box<dyn Write>) -> Self {
+        self.record = Some(sink);
+        self
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Próxima resposta: da gravação ou de `live` (ver [`read_line_from`]).
+    ///
+    /// Uma gravação esgotada é um erro, não um fim de entrada: ela deveria
+    /// cobrir a sessão inteira.
+    pub fn read_line(&mut self, live: &mut dyn BufRead) -> io::Result<InputResult> {
+        let result = match self.replay.as_mut() {
+            Some(recorded) => match recorded.pop_front() {
+                Some(line) => InputResult::Line(line),
+                None => {
+                    return Err(io::Error::other(
+                        "a gravação do --replay acabou antes do fim da sessão",
+                    ))
+                }
+            },
+            None => read_line_from(live)?,
+        };
+        if let (Some(sink), InputResult::Line(line)) = (self.record.as_mut(), &result) {
+            writeln!(sink, "{}", line)?;
+            sink.flush()?;
+        }
+        Ok(result)
+    }
+}
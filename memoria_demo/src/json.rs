@@ -0,0 +1,69 @@
+//! Serialização do [`AddressReport`] em JSON (flag `--json`).
+
+use crate::{render, AddressReport, ValueLayout};
+use serde_json::{json, Value};
+
+/// Versão do esquema JSON; incrementar quando campos mudarem de forma
+/// incompatível para que consumidores possam se adaptar.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// endereço formatado como string hexadecimal (`0x...`, largura de `--width`)
+pub fn hex(addr: usize) -> String {
+    render::hex_address(addr)
+}
+
+fn layout(l: ValueLayout) -> Value {
+    json!({ "size": l.size, "align": l.align })
+}
+
+/// Monta o documento JSON do relatório.
+///
+/// Com `--relative` ativo o documento ganha `relative_base` e `offsets`
+/// (cada endereço menos a base, que é o menor deles); `addresses` continua
+/// absoluto. Com `--fields`, `addresses`, `sizes` e `offsets` levam só as
+/// chaves pedidas.
+pub fn report_to_json(name: &str, birth_year: i32, report: &AddressReport) -> Value {
+    let mut doc = json!({
+        "version": SCHEMA_VERSION,
+        "name": name,
+        "birth_year": birth_year,
+        "current_year": report.current_year,
+        "age": report.age,
+        "addresses": {
+            "welcome": hex(report.welcome),
+            "name": hex(report.name),
+            "name_buffer": hex(report.name_buffer),
+            "stack_value": hex(report.stack_value),
+            "heap_box": hex(report.heap_box),
+            "heap_box_pointee": hex(report.heap_box_pointee),
+            "name_chars": hex(report.name_chars),
+            "name_chars_buffer": hex(report.name_chars_buffer),
+            "example_function": hex(report.example_function),
+        },
+        "sizes": {
+            "stack_value": layout(report.stack_value_layout),
+            "heap_box": layout(report.heap_box_layout),
+            "heap_box_pointee": layout(report.heap_box_pointee_layout),
+            "name": layout(report.name_layout),
+            "name_buffer": layout(report.name_buffer_layout),
+            "name_chars": layout(report.name_chars_layout),
+            "name_chars_buffer": layout(report.name_chars_buffer_layout),
+        },
+    });
+    for section in ["addresses", "sizes"] {
+        if let Some(entries) = doc[section].as_object_mut() {
+            entries.retain(|key, _| render::field_shown(key));
+        }
+    }
+    if let Some(base) = render::relative_base() {
+        let offsets: serde_json::Map<String, Value> = report
+            .labeled()
+            .into_iter()
+            .filter(|(label, _)| render::field_shown(label))
+            .map(|(label, addr)| (label.to_string(), addr.saturating_sub(base).into()))
+            .collect();
+        doc["relative_base"] = hex(base).into();
+        doc["offsets"] = offsets.into();
+    }
+    doc
+}
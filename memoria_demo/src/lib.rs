@@ -0,0 +1,225 @@
+//! memoria_demo — lógica reutilizável de inspeção de memória (stack vs heap).
+//!
+//! O binário (`main.rs`) apenas lê a entrada e imprime; as funções aqui
+//! devolvem valores brutos para que outras ferramentas (ou testes) possam
+//! comparar endereços sem depender do texto impresso.
+
+pub mod age;
+pub mod alloc_stats;
+pub mod ascii_map;
+pub mod bench;
+pub mod checksum;
+pub mod cli;
+pub mod color;
+pub mod csv;
+pub mod demos;
+pub mod diag;
+pub mod diff;
+pub mod dot;
+pub mod exit;
+pub mod hexdump;
+pub mod i18n;
+pub mod input;
+pub mod json;
+pub mod menu;
+pub mod region;
+pub mod render;
+pub mod selfcheck;
+pub mod stack;
+pub mod symbols;
+pub mod tracer;
+
+use chrono::prelude::*;
+use region::Anchors;
+use std::mem;
+
+/// literal estático usado como boas-vindas (tipicamente em .rodata)
+pub const WELCOME: &str = "Bem-vindo ao demo de memória!";
+
+/// Chave estável de cada endereço do [`AddressReport`], na ordem do esquema
+/// JSON (nomes de `--fields`, das chaves JSON e dos labels do CSV).
+pub const FIELDS: [&str; 9] = [
+    "welcome",
+    "name",
+    "name_buffer",
+    "stack_value",
+    "heap_box",
+    "heap_box_pointee",
+    "name_chars",
+    "name_chars_buffer",
+    "example_function",
+];
+
+/// Tamanho e alinhamento de um valor (`size_of_val` / `align_of_val`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueLayout {
+    pub size: usize,
+    pub align: usize,
+}
+
+impl ValueLayout {
+    pub fn of<T: ?Sized>(value: &T) -> Self {
+        ValueLayout {
+            size: mem::size_of_val(value),
+            align: mem::align_of_val(value),
+        }
+    }
+}
+
+/// Endereços (como `usize`) capturados por [`collect_addresses`].
+///
+/// Os valores são apenas observacionais: as alocações e os locais a que se
+/// referem deixam de existir quando `collect_addresses` retorna.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressReport {
+    /// literal `welcome` (.rodata)
+    pub welcome: usize,
+    /// struct `String` do nome (stack)
+    pub name: usize,
+    /// buffer do nome, `as_ptr()` (heap)
+    pub name_buffer: usize,
+    /// `stack_value: i32` (stack)
+    pub stack_value: usize,
+    /// o próprio `Box` (ponteiro na stack)
+    pub heap_box: usize,
+    /// valor apontado pelo `Box` (heap)
+    pub heap_box_pointee: usize,
+    /// struct `Vec<char>` (stack)
+    pub name_chars: usize,
+    /// buffer do `Vec<char>` (heap)
+    pub name_chars_buffer: usize,
+    /// endereço de [`example_function`] (.text)
+    pub example_function: usize,
+    /// layout de `stack_value` (i32)
+    pub stack_value_layout: ValueLayout,
+    /// layout do `Box<i32>` (o ponteiro em si)
+    pub heap_box_layout: ValueLayout,
+    /// layout do `i32` apontado pelo `Box`
+    pub heap_box_pointee_layout: ValueLayout,
+    /// layout da struct `String` (ptr, len, cap)
+    pub name_layout: ValueLayout,
+    /// layout do conteúdo do buffer do nome (`str`)
+    pub name_buffer_layout: ValueLayout,
+    /// layout da struct `Vec<char>` (ptr, len, cap)
+    pub name_chars_layout: ValueLayout,
+    /// layout do conteúdo do buffer do `Vec<char>` (`[char]`)
+    pub name_chars_buffer_layout: ValueLayout,
+    /// `capacity()` do nome, em bytes (pode ser maior que o `len`)
+    pub name_capacity: usize,
+    /// `capacity()` do `Vec<char>`, em elementos (`char` = 4 bytes)
+    pub name_chars_capacity: usize,
+    /// ano atual usado no cálculo da idade
+    pub current_year: i32,
+    /// idade aproximada (`current_year - birth_year`); ver [`age::Birth::age_on`]
+    /// para anos completos a partir da data inteira
+    pub age: i32,
+}
+
+/// Totais de [`AddressReport::totals`], em bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryTotals {
+    pub stack: usize,
+    pub heap: usize,
+}
+
+impl AddressReport {
+    /// Âncoras de região tiradas do próprio relatório: `stack_value`
+    /// (stack), o valor do `Box` (heap), `welcome` (rodata) e
+    /// `example_function` (text).
+    pub fn anchors(&self) -> Anchors {
+        Anchors {
+            stack: self.stack_value,
+            heap: self.heap_box_pointee,
+            rodata: self.welcome,
+            text: self.example_function,
+        }
+    }
+
+    /// Bytes aproximados em cada região: os locais capturados na stack (soma
+    /// dos `size_of`) e o que eles reservaram no heap (capacidades + o `i32`
+    /// do `Box`).
+    pub fn totals(&self) -> MemoryTotals {
+        let stack = self.stack_value_layout.size
+            + self.heap_box_layout.size
+            + self.name_layout.size
+            + self.name_chars_layout.size;
+        let heap = self
+            .name_capacity
+            .saturating_add(self.heap_box_pointee_layout.size)
+            .saturating_add(
+                self.name_chars_capacity
+                    .saturating_mul(mem::size_of::<char>()),
+            );
+        MemoryTotals { stack, heap }
+    }
+
+    /// Cada endereço capturado com o nome do campo, na ordem do esquema JSON.
+    pub fn labeled(&self) -> [(&'static str, usize); 9] {
+        let addrs = [
+            self.welcome,
+            self.name,
+            self.name_buffer,
+            self.stack_value,
+            self.heap_box,
+            self.heap_box_pointee,
+            self.name_chars,
+            self.name_chars_buffer,
+            self.example_function,
+        ];
+        std::array::from_fn(|i| (FIELDS[i], addrs[i]))
+    }
+
+    /// menor dos endereços capturados (base de `--relative`)
+    pub fn lowest_address(&self) -> usize {
+        self.labeled()
+            .into_iter()
+            .map(|(_, addr)| addr)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// Reproduz as alocações da demonstração e devolve os endereços observados.
+pub fn collect_addresses(name: &str, birth_year: i32) -> AddressReport {
+    // 1) Dados estáticos (literal) -> tipicamente armazenado em .rodata
+    let welcome: &str = WELCOME;
+
+    // 2) String (struct na stack, buffer no heap)
+    let name: String = name.to_string();
+
+    // 3) Exemplos de alocações: stack vs heap
+    let stack_value: i32 = 12345;
+    let heap_box = Box::new(2025i32);
+    let name_chars: Vec<char> = name.chars().collect();
+
+    // 4) calcular idade (usa chrono para pegar o ano atual)
+    let current_year = Local::now().year();
+
+    AddressReport {
+        welcome: welcome.as_ptr() as usize,
+        name: &name as *const String as usize,
+        name_buffer: name.as_ptr() as usize,
+        stack_value: &stack_value as *const i32 as usize,
+        heap_box: &heap_box as *const Box<i32> as usize,
+        heap_box_pointee: &*heap_box as *const i32 as usize,
+        name_chars: &name_chars as *const Vec<char> as usize,
+        name_chars_buffer: name_chars.as_ptr() as usize,
+        example_function: example_function as *const () as usize,
+        stack_value_layout: ValueLayout::of(&stack_value),
+        heap_box_layout: ValueLayout::of(&heap_box),
+        heap_box_pointee_layout: ValueLayout::of(&*heap_box),
+        name_layout: ValueLayout::of(&name),
+        name_buffer_layout: ValueLayout::of(name.as_str()),
+        name_chars_layout: ValueLayout::of(&name_chars),
+        name_chars_buffer_layout: ValueLayout::of(name_chars.as_slice()),
+        name_capacity: name.capacity(),
+        name_chars_capacity: name_chars.capacity(),
+        current_year,
+        age: current_year - birth_year,
+    }
+}
+
+/// função auxiliar que existe no segmento de código (.text)
+pub fn example_function() {
+    // corpo vazio — usamos apenas o endereço
+}
@@ -1,80 +1,376 @@
-use chrono::prelude::*;
+use chrono::{Datelike, Local};
+use memoria_demo::age::{self, Birth};
+use memoria_demo::cli::{self, Options, Subcommand};
+use memoria_demo::color::{self, ColorChoice};
+use memoria_demo::demos::{self, DemoContext, MemoryDemo};
+use memoria_demo::exit::Exit;
+use memoria_demo::i18n::{msg, Lang, Msg};
+use memoria_demo::input::{self, InputResult, Session};
+use memoria_demo::menu::{self, Choice};
+use memoria_demo::region::MemoryRegion;
+use memoria_demo::symbols::{SymbolTable, SymbolizingWriter};
+use memoria_demo::{
+    alloc_stats, ascii_map, bench, collect_addresses, csv, diag, diag_debug, diag_info, diff, dot,
+    json, render, selfcheck, stack, AddressReport,
+};
+use std::fs;
 use std::io::{self, Write};
+use std::process::ExitCode;
 
-fn main() {
-    println!("=== memória_demo (Stack vs Heap) ===\n");
+/// contadores de `--alloc-stats`; o alocador fica no binário para não ser
+/// imposto a quem usa a biblioteca
+#[global_allocator]
+static GLOBAL: alloc_stats::CountingAllocator = alloc_stats::CountingAllocator;
 
-    // 1) Dados estáticos (literal) -> tipicamente armazenado em .rodata (segmento de dados do executável)
-    let welcome: &str = "Bem-vindo ao demo de memória!";
-
-    // 2) Entrada do usuário: String (heap) e parsing (ex: birth_year)
-    println!("{}", welcome);
-    let name = read_line("Nome do estudante: ");
-    let birth_year: i32 = loop {
-        let s = read_line("Ano de nascimento (YYYY): ");
-        match s.trim().parse() {
-            Ok(y) => break y,
-            Err(_) => println!("Ano inválido. Tente novamente."),
+fn main() -> ExitCode {
+    // referência da stack: o primeiro local do programa (ver demo stack_base)
+    let base_marker = 0u8;
+    stack::record_main_base(std::hint::black_box(&base_marker) as *const u8 as usize);
+    diag::init();
+    let parsed = cli::parse_args(std::env::args().skip(1)).and_then(|mut opts| {
+        cli::apply_env(&mut opts, |key| std::env::var(key).ok())?;
+        Ok(opts)
+    });
+    let opts = match parsed {
+        Ok(opts) => opts,
+        Err(err) => {
+            eprintln!("erro: {}", err);
+            return err.exit().into();
+        }
+    };
+    diag_debug!("opções: {:?}", opts);
+    diag_info!("idioma: {:?}", opts.lang);
+    if opts.overflow_child {
+        demos::overflow_child();
+    }
+    if opts.code_address_child {
+        demos::code_address_child();
+    }
+    let (machine, lang) = (opts.prompts_to_stderr(), opts.lang);
+    render::set_address_width(opts.width);
+    render::set_fields(&opts.fields);
+    let result = match opts.subcommand {
+        None => run(opts),
+        Some(sub) => run_subcommand(sub, &opts).map(|()| Exit::Success),
+    };
+    match result {
+        Ok(exit) => exit.into(),
+        // stdin fechado (ex: pipe vazio) antes do nome/nascimento: encerramos
+        // com a mensagem de sempre e um código próprio para scripts
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+            if machine {
+                eprintln!("\n{}", msg(Msg::InputClosed, lang));
+            } else {
+                println!("\n{}", msg(Msg::InputClosed, lang));
+            }
+            Exit::InputClosed.into()
         }
+        Err(e) => {
+            eprintln!("erro: {}", e);
+            Exit::Io.into()
+        }
+    }
+}
+
+fn run_subcommand(sub: Subcommand, opts: &Options) -> io::Result<()> {
+    let out = &mut io::stdout().lock();
+    match sub {
+        Subcommand::Aslr => demos::demo_aslr(out),
+        Subcommand::Bench => bench::run_bench(out, bench::ITERATIONS),
+        Subcommand::CompareLaunches => diff::compare_launches(out),
+        Subcommand::Diff => diff::run_diff(out, &opts.operands[0], &opts.operands[1], opts.json),
+    }
+}
+
+/// Fluxo principal (sem subcomando); devolve [`Exit::DemoFailed`] se alguma
+/// demo entrou em pânico.
+fn run(opts: Options) -> io::Result<Exit> {
+    // nos modos --json/--dot/--csv o stdout fica reservado ao documento (e com
+    // --quiet, ao resultado); prompts vão para o stderr
+    let (machine, lang) = (opts.prompts_to_stderr(), opts.lang);
+    if opts.alloc_stats {
+        alloc_stats::enable();
+    }
+    // --only/--skip com nome errado falham antes de qualquer prompt; o nome
+    // do estudante não muda quais demos existem
+    let probe = DemoContext {
+        threads: opts.threads,
+        overflow: opts.demo_overflow,
+        ..DemoContext::new("", 0)
+    };
+    if let Err(e) = demos::select(demos::registry(&probe), &opts.only, &opts.skip) {
+        eprintln!("erro: {}", e);
+        return Ok(Exit::BadArgs);
+    }
+    // respostas gravadas (--replay) e/ou copiadas para um arquivo (--record)
+    let mut session = match &opts.replay {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => Session::replaying(&text),
+            Err(e) => {
+                eprintln!("erro: --replay '{}': {}", path, e);
+                return Ok(Exit::BadInputFile);
+            }
+        },
+        None => Session::live(),
+    };
+    if let Some(path) = &opts.record {
+        let file = fs::File::create(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("--record '{}': {}", path, e)))?;
+        session = session.recording_to(Box::new(file));
+    }
+    // documentos para máquinas nunca levam códigos de escape; com
+    // --no-addresses também não: o escape colado no `0x` esconderia o
+    // endereço do simbolizador
+    color::init(if machine || opts.no_addresses {
+        ColorChoice::Never
+    } else {
+        opts.color
+    });
+    // um único instante para a execução inteira: o JSON usa o mesmo valor
+    let started = Local::now();
+    if opts.timestamp && !opts.machine_output() {
+        println!("{}", render::iso_timestamp(started));
+    }
+    if !machine {
+        println!("{}\n", msg(Msg::Banner, lang));
+        println!("{}", msg(Msg::Welcome, lang));
+    }
+
+    // 1) Entrada do usuário: String (heap) e parsing (ex: birth_year)
+    // valores passados por --name / --birth-year pulam o prompt correspondente
+    let name = match opts.name {
+        Some(name) => name,
+        None => read_line(&mut session, msg(Msg::PromptName, lang), machine)?.into_line()?,
     };
+    // nomes colados gigantes viram Vec<char> e hexdumps gigantes
+    let kept = input::truncate_chars(name.trim(), opts.max_name_len);
+    let name = if kept.len() < name.trim().len() {
+        writeln!(
+            prompt_out(machine),
+            "{} {}",
+            msg(Msg::NameTruncated, lang),
+            opts.max_name_len
+        )?;
+        kept.to_string()
+    } else {
+        name
+    };
+    let birth = match opts.birth {
+        Some(birth) => birth,
+        None => loop {
+            let s =
+                read_line(&mut session, msg(Msg::PromptBirthYear, lang), machine)?.into_line()?;
+            let current_year = Local::now().year();
+            match Birth::parse(&s) {
+                Some(birth) => match age::validate_birth_year(birth.year(), current_year) {
+                    Ok(_) => break birth,
+                    Err(reason) => {
+                        diag_info!("nascimento rejeitado ({}): {:?}", reason, s);
+                        writeln!(
+                            prompt_out(machine),
+                            "{}. {}",
+                            reason,
+                            msg(Msg::InvalidYear, lang)
+                        )?
+                    }
+                },
+                None => {
+                    diag_info!("nascimento ilegível: {:?}", s);
+                    writeln!(prompt_out(machine), "{}", msg(Msg::InvalidYear, lang))?
+                }
+            }
+        },
+    };
+    let birth_year = birth.year();
 
-    // 3) Exemplos de alocações: stack vs heap
-    // stack_value: valor escalar (armazenado na stack como parte do frame atual)
+    // 2) alocações e cálculo da idade ficam na biblioteca
+    let mut report = collect_addresses(name.trim(), birth_year);
+    // com a data completa, a idade passa a ser em anos completos
+    if let Birth::Date(_) = birth {
+        report.age = birth.age_on(Local::now().date_naive());
+    }
+
+    // daqui em diante toda a saída passa pelo mesmo Write (stdout travado);
+    // com --no-addresses ela ainda passa pela tabela de símbolos
+    let stdout = io::stdout();
+    let mut locked = stdout.lock();
+    let mut symbolized;
+    let out: &mut dyn Write = if opts.no_addresses {
+        symbolized = SymbolizingWriter::new(&mut locked, SymbolTable::for_report(&report));
+        &mut symbolized
+    } else {
+        &mut locked
+    };
+
+    if opts.self_check {
+        let checks = selfcheck::run_checks(&report);
+        return Ok(if selfcheck::write_self_check(out, &checks)? {
+            Exit::Success
+        } else {
+            Exit::SelfCheckFailed
+        });
+    }
+
+    // com --relative os endereços viram deslocamentos a partir do menor deles
+    // (no JSON, um campo `offsets` a mais)
+    if opts.relative && !opts.no_addresses {
+        render::set_relative_base(report.lowest_address());
+    }
+    if opts.json {
+        let mut doc = json::report_to_json(name.trim(), birth_year, &report);
+        if opts.timestamp {
+            doc["timestamp"] = render::iso_timestamp(started).into();
+        }
+        writeln!(out, "{}", doc)?;
+        return Ok(Exit::Success);
+    }
+    if opts.dot {
+        write!(out, "{}", dot::report_to_dot(&report))?;
+        return Ok(Exit::Success);
+    }
+    if opts.csv {
+        csv::write_report_csv(out, &report)?;
+        return Ok(Exit::Success);
+    }
+
+    if opts.quiet {
+        render::print_summary(out, &name, birth, &report, lang)?;
+        return Ok(Exit::Success);
+    }
+
+    render::print_report(out, &name, birth, &report, lang)?;
+    if opts.ascii_map {
+        ascii_map::write_ascii_map(out, &report)?;
+    }
+
+    // 4b) com --repeat N, novas coletas no mesmo processo para comparar
+    if opts.repeat > 1 {
+        let reports: Vec<_> = (0..opts.repeat)
+            .map(|_| collect_addresses(name.trim(), birth_year))
+            .collect();
+        render::print_repeat(out, &reports, lang)?;
+    }
+
+    // 5) usar uma função separada para mostrar outro frame de stack (para comparar)
     let stack_value: i32 = 12345;
+    // o endereço de um local deste frame vai junto para comparar a direção da stack
+    let caller_local = &stack_value as *const i32 as usize;
+    // e o &name daqui, para comparar com o valor que o parâmetro recebe
+    let caller_name = &name as *const String as usize;
+    render::show_stack_frame(
+        out,
+        &name,
+        stack_value,
+        caller_local,
+        caller_name,
+        &report.anchors(),
+        lang,
+    )?;
 
-    // heap_box: Box aloca no heap (o Box em si (pointer) fica na stack; o valor apontado fica na heap)
-    let heap_box = Box::new(2025i32);
-
-    // name_chars: Vec<char> (estrutura no stack, buffer no heap)
-    let name_chars: Vec<char> = name.chars().collect();
-
-    // 4) calcular idade (usa chrono para pegar o ano atual)
-    let now = Local::now();
-    let current_year = now.year();
-    let age = current_year - birth_year;
-
-    println!("\n--- Resultado ---");
-    println!("Nome (String)   : {}", name.trim());
-    println!("Ano nascimento  : {}", birth_year);
-    println!("Ano atual       : {}", current_year);
-    println!("Idade aproximada: {} anos\n", age);
-
-    // 5) Mostrar endereços e demonstrar onde cada coisa vive (observacional)
-    println!("--- Endereços / Pistas de memória ---");
-    println!("&welcome (literal .rodata)      = {:p}", welcome as *const str);
-    println!("name (String object on stack)   = {:p}", &name);
-    println!("name buffer (heap) as_ptr()     = {:p}", name.as_ptr());
-    println!("stack_value (stack)             = {:p}", &stack_value);
-    println!("heap_box pointer (on stack)     = {:p}", &heap_box);
-    println!("heap_box pointee (heap)         = {:p}", &*heap_box);
-    println!("name_chars Vec struct (stack)   = {:p}", &name_chars);
-    println!("name_chars buffer (heap)        = {:p}", name_chars.as_ptr());
-
-    // 6) endereço de função (código -> typically in .text)
-    println!("example_function (endereço código) = {:p}", example_function as *const ());
-
-    // 7) usar uma função separada para mostrar outro frame de stack (para comparar)
-    show_stack_frame(&name, stack_value);
-
-    println!("\n(Dica) Para inspecionar o binário/assembly: veja seção 'Ver binário / assembly' no README.");
+    // 6) demonstrações extras, escolhidas no menu
+    let context = DemoContext {
+        recursion_depth: opts.depth,
+        threads: opts.threads,
+        overflow: opts.demo_overflow,
+        stack_items: stack_items(&report),
+        ..DemoContext::new(name.trim(), report.age)
+    };
+    let registry = demos::select(demos::registry(&context), &opts.only, &opts.skip)
+        .map_err(io::Error::other)?;
+    let selected: Vec<&dyn MemoryDemo> = match choose_demo(out, &registry, lang, &mut session)? {
+        Choice::All => registry.iter().map(|demo| demo.as_ref()).collect(),
+        Choice::One(i) => vec![registry[i].as_ref()],
+    };
+    let failed = demos::run_demos(out, &selected, opts.fail_fast, lang)?;
+
+    render::print_totals(out, &report, lang)?;
+    writeln!(out, "\n{}", msg(Msg::Tip, lang))?;
+
+    if alloc_stats::is_enabled() {
+        let stats = alloc_stats::snapshot();
+        writeln!(
+            out,
+            "{}: {}, {}: {}",
+            msg(Msg::AllocTotal, lang),
+            stats.allocated,
+            msg(Msg::AllocLive, lang),
+            stats.live()
+        )?;
+    }
+    if failed.is_empty() {
+        Ok(Exit::Success)
+    } else {
+        writeln!(
+            out,
+            "{} {}",
+            msg(Msg::DemosFailedSummary, lang),
+            failed.join(", ")
+        )?;
+        Ok(Exit::DemoFailed)
+    }
 }
 
-/// função auxiliar que existe no segmento de código (.text)
-fn example_function() {
-    // corpo vazio — usamos apenas o endereço
+/// itens do relatório que as âncoras classificam como stack
+fn stack_items(report: &AddressReport) -> Vec<(&'static str, usize)> {
+    let anchors = report.anchors();
+    report
+        .labeled()
+        .into_iter()
+        .filter(|&(_, addr)| anchors.classify(addr) == MemoryRegion::Stack)
+        .collect()
 }
 
-fn read_line(prompt: &str) -> String {
-    print!("{}", prompt);
-    io::stdout().flush().expect("flush failed");
-    let mut s = String::new();
-    io::stdin().read_line(&mut s).expect("failed to read");
-    s
+/// Lista as demos numeradas e lê a escolha, repetindo em caso de opção inválida.
+///
+/// Sem entrada (EOF, ex: stdin de um pipe já consumido) rodam todas, como
+/// antes de existir o menu.
+fn choose_demo(
+    out: &mut dyn Write,
+    registry: &[Box<dyn MemoryDemo>],
+    lang: Lang,
+    session: &mut Session,
+) -> io::Result<Choice> {
+    writeln!(out, "\n{}", msg(Msg::MenuHeader, lang))?;
+    for (i, demo) in registry.iter().enumerate() {
+        writeln!(out, "{:>2}) {}", i + 1, demo.name())?;
+    }
+    writeln!(out, " t) {}", msg(Msg::MenuAll, lang))?;
+    out.flush()?;
+    loop {
+        let line = match read_line(session, msg(Msg::MenuPrompt, lang), false)? {
+            InputResult::Line(line) => line,
+            InputResult::Eof => return Ok(Choice::All),
+        };
+        match menu::parse_choice(&line, registry.len()) {
+            Some(choice) => return Ok(choice),
+            None => {
+                diag_info!("opção de menu rejeitada: {:?}", line);
+                writeln!(out, "{}", msg(Msg::MenuInvalid, lang))?
+            }
+        }
+    }
 }
 
-fn show_stack_frame(name: &String, local: i32) {
-    // esse frame terá seus próprios locais na stack; imprimimos endereços para comparar
-    println!("\n--- Dentro de outra função (novo frame na stack) ---");
-    println!("param name (referência) addr = {:p}", name);
-    println!("local (i32) addr              = {:p}", &local);
-}
\ No newline at end of file
+/// destino das mensagens interativas: stderr nos modos de saída para
+/// máquinas (`--json`, `--dot`, `--csv`) e com `--quiet`, stdout caso contrário
+fn prompt_out(machine: bool) -> Box<dyn Write> {
+    if machine {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    }
+}
+
+/// mostra o prompt e lê uma resposta do stdin ou da gravação (ver
+/// [`Session::read_line`]); respostas gravadas são ecoadas após o prompt,
+/// como se tivessem sido digitadas
+fn read_line(session: &mut Session, prompt: &str, machine: bool) -> io::Result<InputResult> {
+    let mut out = prompt_out(machine);
+    write!(out, "{}", prompt)?;
+    out.flush()?;
+    let result = session.read_line(&mut io::stdin().lock())?;
+    if let (true, InputResult::Line(line)) = (session.is_replaying(), &result) {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,24 @@
+//! Menu de seleção das demonstrações.
+
+/// Escolha feita no menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    /// rodar todas as demonstrações
+    All,
+    /// rodar só a demonstração de índice (base 0) dado
+    One(usize),
+}
+
+/// Interpreta a resposta do menu para `count` demos numeradas a partir de 1.
+///
+/// Linha vazia, `t`, `todos` ou `all` escolhem todas.
+pub fn parse_choice(input: &str, count: usize) -> Option<Choice> {
+    let input = input.trim().to_ascii_lowercase();
+    match input.as_str() {
+        "" | "t" | "todos" | "all" => Some(Choice::All),
+        n => match n.parse::<usize>() {
+            Ok(i) if (1..=count).contains(&i) => Some(Choice::One(i - 1)),
+            _ => None,
+        },
+    }
+}
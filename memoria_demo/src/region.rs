@@ -0,0 +1,96 @@
+//! Classificação heurística de endereços em regiões de memória.
+//!
+//! Não existe API portável para perguntar "este ponteiro está na stack?".
+//! Em vez disso comparamos o endereço com âncoras capturadas em tempo de
+//! execução (um local na stack, uma alocação no heap, uma função e um
+//! literal) e escolhemos a âncora mais próxima. É best-effort: serve para
+//! ilustrar, não para decidir nada importante.
+
+use std::fmt;
+
+/// endereços abaixo disto nunca são válidos (primeira página não é mapeada);
+/// aparecem como ponteiros "pendurados" de tipos de tamanho zero
+const NULL_PAGE: usize = 4096;
+
+/// Região de memória inferida para um endereço.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryRegion {
+    Stack,
+    Heap,
+    Rodata,
+    Text,
+    Unknown,
+}
+
+impl MemoryRegion {
+    pub fn label(self) -> &'static str {
+        match self {
+            MemoryRegion::Stack => "stack",
+            MemoryRegion::Heap => "heap",
+            MemoryRegion::Rodata => "rodata",
+            MemoryRegion::Text => "text",
+            MemoryRegion::Unknown => "desconhecida",
+        }
+    }
+}
+
+impl fmt::Display for MemoryRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Endereços de referência, um por região conhecida.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Anchors {
+    pub stack: usize,
+    pub heap: usize,
+    pub rodata: usize,
+    pub text: usize,
+}
+
+impl Anchors {
+    /// Captura âncoras novas a partir do frame atual.
+    ///
+    /// O local e o `Box` deixam de existir no retorno, mas a stack e o heap
+    /// continuam ocupando as mesmas faixas de endereço — é isso que importa.
+    pub fn capture() -> Self {
+        let local = 0u8;
+        let boxed = Box::new(0u8);
+        Anchors {
+            stack: &local as *const u8 as usize,
+            heap: &*boxed as *const u8 as usize,
+            rodata: crate::WELCOME.as_ptr() as usize,
+            text: crate::example_function as *const () as usize,
+        }
+    }
+
+    /// Classifica `addr` pela âncora mais próxima.
+    ///
+    /// Suposições de ordem (típicas de Linux/macOS em x86_64 e aarch64):
+    /// a imagem do executável (.text e .rodata) fica em endereços baixos, o
+    /// heap logo acima dela e a stack perto do topo do espaço de endereços,
+    /// crescendo para baixo. Como as regiões são bem separadas, a distância
+    /// até a âncora de cada uma basta para separá-las; .text e .rodata, por
+    /// estarem vizinhas, são as que mais podem ser confundidas.
+    pub fn classify(&self, addr: usize) -> MemoryRegion {
+        if addr < NULL_PAGE {
+            return MemoryRegion::Unknown;
+        }
+        [
+            (self.stack, MemoryRegion::Stack),
+            (self.heap, MemoryRegion::Heap),
+            (self.rodata, MemoryRegion::Rodata),
+            (self.text, MemoryRegion::Text),
+        ]
+        .into_iter()
+        .min_by_key(|&(anchor, _)| anchor.abs_diff(addr))
+        .map(|(_, region)| region)
+        .unwrap_or(MemoryRegion::Unknown)
+    }
+}
+
+/// Classifica `addr` usando âncoras capturadas na hora (ver [`Anchors::classify`]).
+pub fn classify(addr: usize) -> MemoryRegion {
+    Anchors::capture().classify(addr)
+}
@@ -0,0 +1,362 @@
+//! Impressão do relatório principal em qualquer `Write` (stdout ou buffer).
+
+use crate::age::Birth;
+use crate::color;
+use crate::i18n::{msg, Lang, Msg};
+use crate::region::{Anchors, MemoryRegion};
+use crate::stack::{self, StackGrowth};
+use crate::{AddressReport, ValueLayout};
+use chrono::{DateTime, Local, SecondsFormat};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// base de `--relative`; 0 = endereços absolutos
+static RELATIVE_BASE: AtomicUsize = AtomicUsize::new(0);
+
+/// Passa a imprimir os endereços como deslocamentos a partir de `base`
+/// (`--relative`), para poder comparar execuções apesar do ASLR.
+pub fn set_relative_base(base: usize) {
+    RELATIVE_BASE.store(base, Ordering::Relaxed);
+}
+
+/// base de `--relative`, se ativo
+pub fn relative_base() -> Option<usize> {
+    match RELATIVE_BASE.load(Ordering::Relaxed) {
+        0 => None,
+        base => Some(base),
+    }
+}
+
+/// dígitos hex de `--width`; 0 = `auto` (largura do ponteiro)
+static ADDRESS_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Fixa quantos dígitos hex cada endereço ocupa (`--width`); `None` volta
+/// ao `auto`.
+pub fn set_address_width(width: Option<usize>) {
+    ADDRESS_WIDTH.store(width.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// dígitos hex por endereço: o de `--width` ou `2 * size_of::<usize>()`
+pub fn address_width() -> usize {
+    match ADDRESS_WIDTH.load(Ordering::Relaxed) {
+        0 => std::mem::size_of::<usize>() * 2,
+        width => width,
+    }
+}
+
+/// índices de [`crate::FIELDS`] impressos (`--fields`); um bit por campo
+static FIELD_MASK: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Restringe o relatório, o JSON e o CSV às chaves de `fields` (`--fields`);
+/// vazio volta a mostrar tudo. Chaves desconhecidas são ignoradas (a CLI já
+/// as rejeita).
+pub fn set_fields(fields: &[String]) {
+    let mask = if fields.is_empty() {
+        usize::MAX
+    } else {
+        crate::FIELDS
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| fields.iter().any(|f| f == *key))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    };
+    FIELD_MASK.store(mask, Ordering::Relaxed);
+}
+
+/// `true` se o campo `key` deve sair (ver [`set_fields`])
+pub fn field_shown(key: &str) -> bool {
+    let mask = FIELD_MASK.load(Ordering::Relaxed);
+    crate::FIELDS
+        .iter()
+        .position(|f| *f == key)
+        .is_none_or(|i| mask & (1 << i) != 0)
+}
+
+/// `0x` + o endereço com zeros à esquerda até [`address_width`] (nunca
+/// trunca: um endereço mais longo sai inteiro)
+pub fn hex_address(addr: usize) -> String {
+    format!("0x{:0width$x}", addr, width = address_width())
+}
+
+/// [`hex_address`] de um ponteiro ou referência (o que as demos imprimiriam
+/// com `{:p}`)
+pub fn pointer_address<T: ?Sized>(ptr: *const T) -> String {
+    hex_address(ptr.cast::<()>() as usize)
+}
+
+/// `0x...` absoluto ou, com `--relative`, `base + 0x...` / `base - 0x...`
+pub fn format_address(addr: usize) -> String {
+    match RELATIVE_BASE.load(Ordering::Relaxed) {
+        0 => hex_address(addr),
+        base if addr >= base => format!("base + {:#x}", addr - base),
+        base => format!("base - {:#x}", base - addr),
+    }
+}
+
+/// Cabeçalho de seção usado por todas as demos: linha em branco e
+/// `--- título ---`, em negrito quando as cores estão ligadas (`--color
+/// never` e `--quiet` imprimem o texto puro).
+pub fn section(out: &mut dyn Write, title: &str) -> io::Result<()> {
+    writeln!(out, "\n{}", color::bold(&format!("--- {} ---", title)))
+}
+
+/// data e hora locais em ISO 8601, com fuso (`--timestamp`)
+pub fn iso_timestamp(now: DateTime<Local>) -> String {
+    now.to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+/// Só o resultado: nome, nascimento e idade (tudo o que `--quiet` imprime).
+pub fn print_summary(
+    out: &mut dyn Write,
+    name: &str,
+    birth: Birth,
+    report: &AddressReport,
+    lang: Lang,
+) -> io::Result<()> {
+    let t = |key| msg(key, lang);
+    writeln!(out, "\n{}", t(Msg::ResultHeader))?;
+    writeln!(out, "{}{}", t(Msg::LabelName), name.trim())?;
+    match birth {
+        Birth::Year(year) => writeln!(out, "{}{}", t(Msg::LabelBirthYear), year)?,
+        Birth::Date(date) => writeln!(out, "{}{}", t(Msg::LabelBirthYear), date)?,
+    }
+    writeln!(out, "{}{}", t(Msg::LabelCurrentYear), report.current_year)?;
+    let age_label = match birth {
+        Birth::Year(_) => Msg::LabelAge,
+        Birth::Date(_) => Msg::LabelExactAge,
+    };
+    writeln!(out, "{}{} {}", t(age_label), report.age, t(Msg::YearsUnit))
+}
+
+/// Resultado, endereços com região inferida e tabela de tamanhos.
+pub fn print_report(
+    out: &mut dyn Write,
+    name: &str,
+    birth: Birth,
+    report: &AddressReport,
+    lang: Lang,
+) -> io::Result<()> {
+    print_summary(out, name, birth, report, lang)?;
+    let t = |key| msg(key, lang);
+    writeln!(out)?;
+
+    // 3) Mostrar endereços e demonstrar onde cada coisa vive (observacional)
+    writeln!(out, "{}", t(Msg::AddressesHeader))?;
+    let base = RELATIVE_BASE.load(Ordering::Relaxed);
+    if base != 0 {
+        writeln!(out, "{:<34} = {}", t(Msg::RelativeBase), hex_address(base))?;
+    }
+    let anchors = report.anchors();
+    let addr = |out: &mut dyn Write, field: &str, key, addr: usize| {
+        if field_shown(field) {
+            print_address(out, t(key), addr, &anchors, lang)
+        } else {
+            Ok(())
+        }
+    };
+    addr(out, "welcome", Msg::AddrWelcome, report.welcome)?;
+    addr(out, "name", Msg::AddrName, report.name)?;
+    addr(out, "name_buffer", Msg::AddrNameBuffer, report.name_buffer)?;
+    addr(out, "stack_value", Msg::AddrStackValue, report.stack_value)?;
+    addr(out, "heap_box", Msg::AddrHeapBox, report.heap_box)?;
+    addr(
+        out,
+        "heap_box_pointee",
+        Msg::AddrHeapBoxPointee,
+        report.heap_box_pointee,
+    )?;
+    addr(out, "name_chars", Msg::AddrNameChars, report.name_chars)?;
+    addr(
+        out,
+        "name_chars_buffer",
+        Msg::AddrNameCharsBuffer,
+        report.name_chars_buffer,
+    )?;
+
+    // 3b) tamanho (size_of_val) e alinhamento (align_of_val) de cada valor
+    writeln!(out, "\n{}", t(Msg::SizesHeader))?;
+    print_row(out, t(Msg::SizesValueColumn), "size_of_val", "align_of_val")?;
+    print_layout(out, "stack_value (i32, stack)", report.stack_value_layout)?;
+    print_layout(out, "heap_box (Box<i32>, stack)", report.heap_box_layout)?;
+    print_layout(out, "*heap_box (i32, heap)", report.heap_box_pointee_layout)?;
+    print_layout(out, "name (String struct, stack)", report.name_layout)?;
+    print_layout(out, "name buffer (str, heap)", report.name_buffer_layout)?;
+    print_layout(
+        out,
+        "name_chars (Vec struct, stack)",
+        report.name_chars_layout,
+    )?;
+    print_layout(
+        out,
+        "name_chars buffer ([char], heap)",
+        report.name_chars_buffer_layout,
+    )?;
+    writeln!(out)?;
+
+    // 4) endereço de função (código -> typically in .text)
+    addr(
+        out,
+        "example_function",
+        Msg::AddrExampleFunction,
+        report.example_function,
+    )
+}
+
+/// endereços do relatório agrupados pela região onde cada um deve morar
+fn addresses_by_region(report: &AddressReport) -> [(MemoryRegion, Vec<usize>); 4] {
+    [
+        (
+            MemoryRegion::Stack,
+            vec![
+                report.name,
+                report.stack_value,
+                report.heap_box,
+                report.name_chars,
+            ],
+        ),
+        (
+            MemoryRegion::Heap,
+            vec![
+                report.name_buffer,
+                report.heap_box_pointee,
+                report.name_chars_buffer,
+            ],
+        ),
+        (MemoryRegion::Rodata, vec![report.welcome]),
+        (MemoryRegion::Text, vec![report.example_function]),
+    ]
+}
+
+/// Uma linha por coleta (`--repeat N`) e, no fim, se os endereços de cada
+/// região ficaram iguais em todas elas.
+pub fn print_repeat(out: &mut dyn Write, reports: &[AddressReport], lang: Lang) -> io::Result<()> {
+    let t = |key| msg(key, lang);
+    writeln!(out, "\n{}", t(Msg::RepeatHeader))?;
+    for (i, report) in reports.iter().enumerate() {
+        writeln!(
+            out,
+            "{} {:>3}: stack_value = {}, heap_box pointee = {}, name buffer = {}",
+            t(Msg::RepeatIteration),
+            i + 1,
+            format_address(report.stack_value),
+            format_address(report.heap_box_pointee),
+            format_address(report.name_buffer)
+        )?;
+    }
+    let Some(first) = reports.first() else {
+        return Ok(());
+    };
+    for (i, (region, addrs)) in addresses_by_region(first).into_iter().enumerate() {
+        let stable = reports.iter().all(|r| addresses_by_region(r)[i].1 == addrs);
+        writeln!(
+            out,
+            "{} ({}): {}",
+            t(Msg::RepeatStable),
+            region,
+            t(if stable { Msg::Yes } else { Msg::No })
+        )?;
+    }
+    writeln!(out)
+}
+
+/// imprime um endereço seguido da região inferida por `classify`
+pub fn print_address(
+    out: &mut dyn Write,
+    label: &str,
+    addr: usize,
+    anchors: &Anchors,
+    lang: Lang,
+) -> io::Result<()> {
+    let region = anchors.classify(addr);
+    writeln!(
+        out,
+        "{:<34} = {}  [{}: {}]",
+        label,
+        color::paint(region, &format_address(addr)),
+        msg(Msg::Inferred, lang),
+        color::paint(region, region.label())
+    )
+}
+
+/// imprime uma linha da tabela de tamanhos com colunas alinhadas
+pub fn print_row(out: &mut dyn Write, label: &str, size: &str, align: &str) -> io::Result<()> {
+    writeln!(out, "{:<34} {:>12} {:>13}", label, size, align)
+}
+
+pub fn print_layout(out: &mut dyn Write, label: &str, layout: ValueLayout) -> io::Result<()> {
+    print_row(
+        out,
+        label,
+        &layout.size.to_string(),
+        &layout.align.to_string(),
+    )
+}
+
+/// Novo frame de stack: o ponteiro recebido no parâmetro (comparado com
+/// `caller_name`, o `&name` do chamador), o endereço do próprio parâmetro,
+/// um local e a direção de crescimento em relação a `caller_local`.
+pub fn show_stack_frame(
+    out: &mut dyn Write,
+    name: &String,
+    local: i32,
+    caller_local: usize,
+    caller_name: usize,
+    anchors: &Anchors,
+    lang: Lang,
+) -> io::Result<()> {
+    let t = |key| msg(key, lang);
+    // esse frame terá seus próprios locais na stack; imprimimos endereços para comparar
+    writeln!(out, "\n{}", t(Msg::FrameHeader))?;
+    // o valor do parâmetro é o ponteiro recebido (aponta para o String do
+    // chamador); o parâmetro em si ocupa um slot novo neste frame
+    let received = name as *const String as usize;
+    print_address(out, t(Msg::AddrParam), received, anchors, lang)?;
+    print_address(out, t(Msg::CallerNameAddr), caller_name, anchors, lang)?;
+    print_address(
+        out,
+        t(Msg::AddrParamSlot),
+        &name as *const &String as usize,
+        anchors,
+        lang,
+    )?;
+    writeln!(
+        out,
+        "{} {}",
+        t(if received == caller_name {
+            Msg::ParamIsCallerPointer
+        } else {
+            Msg::ParamIsNotCallerPointer
+        }),
+        t(Msg::ParamCopiedNote)
+    )?;
+    let local_addr = &local as *const i32 as usize;
+    print_address(out, t(Msg::AddrLocal), local_addr, anchors, lang)?;
+
+    // frame mais profundo em endereço menor => a stack cresce para baixo
+    let (relation, conclusion) = match stack::growth_between(caller_local, local_addr) {
+        StackGrowth::Down => (Msg::DeeperFrameLower, Msg::StackGrowsDown),
+        StackGrowth::Up => (Msg::DeeperFrameHigher, Msg::StackGrowsUp),
+    };
+    writeln!(
+        out,
+        "{} = {}; {}",
+        t(Msg::CallerLocal),
+        format_address(caller_local),
+        t(relation)
+    )?;
+    writeln!(out, "{}", t(conclusion))
+}
+
+/// Rodapé com o total aproximado de bytes na stack e no heap.
+pub fn print_totals(out: &mut dyn Write, report: &AddressReport, lang: Lang) -> io::Result<()> {
+    let t = |key| msg(key, lang);
+    let totals = report.totals();
+    writeln!(out, "\n{}", t(Msg::TotalsHeader))?;
+    writeln!(
+        out,
+        "{:<44} = {:>5} bytes",
+        t(Msg::TotalsStack),
+        totals.stack
+    )?;
+    writeln!(out, "{:<44} = {:>5} bytes", t(Msg::TotalsHeap), totals.heap)
+}
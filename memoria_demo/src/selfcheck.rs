@@ -0,0 +1,100 @@
+//! `--self-check`: confere as relações de memória que o demo ensina.
+//!
+//! Cada verificação leva o nome do conceito que ela confirma; uma falha não
+//! é necessariamente um bug do programa, mas significa que a plataforma se
+//! comporta diferente do que o texto das demos afirma.
+//!
+//! Buffers do heap não são classificados aqui: com várias arenas (uma por
+//! thread no glibc) um buffer pode cair longe da âncora de heap, e a
+//! heurística de [`crate::region`] erraria sem que o conceito esteja errado.
+
+use crate::demos;
+use crate::region::MemoryRegion;
+use crate::AddressReport;
+use std::io::{self, Write};
+use std::mem;
+use std::ptr;
+
+/// Uma verificação e o seu resultado.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// conceito verificado, em uma frase
+    pub concept: &'static str,
+    pub passed: bool,
+}
+
+/// literal repetido de propósito para [`run_checks`]
+fn literal_pair() -> (*const u8, *const u8) {
+    let first: &str = "auto-verificação";
+    let second: &str = "auto-verificação";
+    (first.as_ptr(), second.as_ptr())
+}
+
+/// Roda todas as verificações sobre `report` (e algumas alocações novas).
+pub fn run_checks(report: &AddressReport) -> Vec<Check> {
+    let anchors = report.anchors();
+    let word = mem::size_of::<usize>();
+    let (first_literal, second_literal) = literal_pair();
+    let (raw, value, back) = demos::raw_round_trip(Box::new(7u32));
+    let (owned, view) = demos::as_str_pointers("Ana");
+    let float = Box::new(1.0f64);
+    let float_addr = &*float as *const f64 as usize;
+
+    let check = |concept, passed| Check { concept, passed };
+    vec![
+        check(
+            "String: o handle (stack) e o buffer (heap) têm endereços diferentes",
+            report.name != report.name_buffer,
+        ),
+        check(
+            "Box: o ponteiro e o valor apontado têm endereços diferentes",
+            report.heap_box != report.heap_box_pointee,
+        ),
+        check(
+            "Vec: o handle (stack) e o buffer (heap) têm endereços diferentes",
+            report.name_chars != report.name_chars_buffer,
+        ),
+        check(
+            "handles de String e Vec são classificados como stack",
+            anchors.classify(report.name) == MemoryRegion::Stack
+                && anchors.classify(report.name_chars) == MemoryRegion::Stack,
+        ),
+        check(
+            "literais idênticos compartilham o endereço no .rodata",
+            ptr::eq(first_literal, second_literal),
+        ),
+        check(
+            "Box<i32> ocupa um ponteiro na stack",
+            report.heap_box_layout.size == word,
+        ),
+        check(
+            "String e Vec são (ptr, len, cap): três words",
+            report.name_layout.size == 3 * word && report.name_chars_layout.size == 3 * word,
+        ),
+        check(
+            "Box::into_raw/from_raw preserva o endereço e o valor",
+            ptr::eq(raw, &*back) && value == 7,
+        ),
+        check(
+            "as_str() aponta para o mesmo buffer da String",
+            owned == view,
+        ),
+        check(
+            "o alocador respeita o alinhamento (Box<f64> múltiplo de 8)",
+            float_addr.is_multiple_of(mem::align_of::<f64>()),
+        ),
+    ]
+}
+
+/// Escreve uma linha `[ok]`/`[FALHA]` por verificação e o total; devolve
+/// `true` se todas passaram.
+pub fn write_self_check(out: &mut dyn Write, checks: &[Check]) -> io::Result<bool> {
+    writeln!(out, "--- Auto-verificação ---")?;
+    for check in checks {
+        let mark = if check.passed { "[ok]   " } else { "[FALHA]" };
+        writeln!(out, "{} {}", mark, check.concept)?;
+    }
+    let passed = checks.iter().filter(|c| c.passed).count();
+    writeln!(out, "{}/{} verificações passaram", passed, checks.len())?;
+    Ok(passed == checks.len())
+}
@@ -0,0 +1,52 @@
+//! Observações sobre a stack: direção de crescimento entre frames.
+
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// endereço de um local do início de `main`; 0 = não registrado
+static MAIN_BASE: AtomicUsize = AtomicUsize::new(0);
+
+/// Registra o endereço de um local capturado logo no início de `main`: a
+/// base aproximada da stack do programa (abaixo dele só há o runtime).
+pub fn record_main_base(addr: usize) {
+    MAIN_BASE.store(addr, Ordering::Relaxed);
+}
+
+/// base registrada por [`record_main_base`], se houver
+pub fn main_base() -> Option<usize> {
+    match MAIN_BASE.load(Ordering::Relaxed) {
+        0 => None,
+        base => Some(base),
+    }
+}
+
+/// Direção em que a stack cresce quando uma função chama outra.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackGrowth {
+    /// o frame mais profundo fica em endereço menor
+    Down,
+    /// o frame mais profundo fica em endereço maior
+    Up,
+}
+
+/// Compara o endereço de um local do chamador com o de um local do frame chamado.
+pub fn growth_between(caller_local: usize, callee_local: usize) -> StackGrowth {
+    if callee_local < caller_local {
+        StackGrowth::Down
+    } else {
+        StackGrowth::Up
+    }
+}
+
+/// Mede a direção chamando uma função que não pode ser inlined.
+pub fn measure_growth() -> StackGrowth {
+    let outer = 0u8;
+    let outer_addr = black_box(&outer) as *const u8 as usize;
+    growth_between(outer_addr, deeper_local_addr())
+}
+
+#[inline(never)]
+fn deeper_local_addr() -> usize {
+    let inner = 0u8;
+    black_box(&inner) as *const u8 as usize
+}
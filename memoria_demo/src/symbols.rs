@@ -0,0 +1,166 @@
+//! Nomes simbólicos no lugar de endereços (flag `--no-addresses`).
+//!
+//! A saída passa por [`SymbolizingWriter`], que troca cada `0x...` que
+//! pareça um endereço por um nome como `<stack:name>` ou `<heap:buffer#1>`.
+//! O mesmo endereço sempre recebe o mesmo nome, então as relações (este
+//! ponteiro aponta para aquele buffer) continuam visíveis.
+
+use crate::region::{Anchors, MemoryRegion};
+use crate::AddressReport;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// números mais longe que isto de toda âncora não são tratados como
+/// endereço (ex.: checksums, valores impressos em hex)
+const MAX_ANCHOR_DISTANCE: usize = 1 << 32;
+
+/// Tabela endereço -> nome, preenchida à medida que endereços aparecem.
+pub struct SymbolTable {
+    anchors: Anchors,
+    names: HashMap<usize, String>,
+    counters: HashMap<MemoryRegion, usize>,
+}
+
+impl SymbolTable {
+    pub fn new(anchors: Anchors) -> Self {
+        SymbolTable {
+            anchors,
+            names: HashMap::new(),
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Tabela já com os nomes dos itens do relatório principal.
+    pub fn for_report(report: &AddressReport) -> Self {
+        let mut table = SymbolTable::new(report.anchors());
+        for (addr, name) in [
+            (report.welcome, "welcome"),
+            (report.name, "name"),
+            (report.name_buffer, "name_buffer"),
+            (report.stack_value, "stack_value"),
+            (report.heap_box, "heap_box"),
+            (report.heap_box_pointee, "heap_box_pointee"),
+            (report.name_chars, "name_chars"),
+            (report.name_chars_buffer, "name_chars_buffer"),
+            (report.example_function, "example_function"),
+        ] {
+            let region = table.anchors.classify(addr);
+            table
+                .names
+                .entry(addr)
+                .or_insert_with(|| format!("<{}:{}>", region, name));
+        }
+        table
+    }
+
+    fn looks_like_address(&self, addr: usize) -> bool {
+        let a = &self.anchors;
+        self.anchors.classify(addr) != MemoryRegion::Unknown
+            && [a.stack, a.heap, a.rodata, a.text]
+                .iter()
+                .any(|&anchor| anchor.abs_diff(addr) < MAX_ANCHOR_DISTANCE)
+    }
+
+    /// Nome de `addr`, criando um novo (`<heap:buffer#1>`, ...) na primeira
+    /// vez; `None` se o número não parece um endereço.
+    pub fn symbol(&mut self, addr: usize) -> Option<String> {
+        if let Some(name) = self.names.get(&addr) {
+            return Some(name.clone());
+        }
+        if !self.looks_like_address(addr) {
+            return None;
+        }
+        let region = self.anchors.classify(addr);
+        let counter = self.counters.entry(region).or_insert(0);
+        *counter += 1;
+        let kind = match region {
+            MemoryRegion::Stack => "local",
+            MemoryRegion::Heap => "buffer",
+            MemoryRegion::Rodata => "data",
+            MemoryRegion::Text | MemoryRegion::Unknown => "fn",
+        };
+        let name = format!("<{}:{}#{}>", region, kind, counter);
+        self.names.insert(addr, name.clone());
+        Some(name)
+    }
+
+    /// Troca os tokens `0x...` de `line` que forem endereços.
+    pub fn symbolize_line(&mut self, line: &str) -> String {
+        let bytes = line.as_bytes();
+        let mut result = String::with_capacity(line.len());
+        let mut i = 0;
+        let mut copied = 0;
+        while i + 1 < bytes.len() {
+            let at_boundary = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+            if at_boundary && bytes[i] == b'0' && bytes[i + 1] == b'x' {
+                let digits = bytes[i + 2..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_hexdigit())
+                    .count();
+                let end = i + 2 + digits;
+                let symbol = usize::from_str_radix(&line[i + 2..end], 16)
+                    .ok()
+                    .and_then(|addr| self.symbol(addr));
+                if let Some(symbol) = symbol {
+                    result.push_str(&line[copied..i]);
+                    result.push_str(&symbol);
+                    copied = end;
+                }
+                i = end.max(i + 1);
+            } else {
+                i += 1;
+            }
+        }
+        result.push_str(&line[copied..]);
+        result
+    }
+}
+
+/// `Write` que junta a saída linha a linha e simboliza cada linha antes de
+/// repassá-la (um endereço pode chegar em mais de um `write`).
+pub struct SymbolizingWriter<W: Write> {
+    inner: W,
+    table: SymbolTable,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> SymbolizingWriter<W> {
+    pub fn new(inner: W, table: SymbolTable) -> Self {
+        SymbolizingWriter {
+            inner,
+            table,
+            pending: Vec::new(),
+        }
+    }
+
+    fn emit(&mut self, chunk: &[u8]) -> io::Result<()> {
+        let line = self.table.symbolize_line(&String::from_utf8_lossy(chunk));
+        self.inner.write_all(line.as_bytes())
+    }
+}
+
+impl<W: Write> Write for SymbolizingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        while let Some(pos) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            self.emit(&line)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            self.emit(&rest)?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for SymbolizingWriter<W> {
+    fn drop(&mut self) {
+        // erros aqui não têm para onde ir; a saída normal já deu flush
+        let _ = self.flush();
+    }
+}
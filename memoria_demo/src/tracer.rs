@@ -0,0 +1,69 @@
+//! `Tracer`: um valor que anuncia quando é destruído (via `Drop`).
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// destino opcional dos drops (ver [`capture_drops`]); `None` = imprimir
+    static DROP_SINK: RefCell<Option<Vec<&'static str>>> = const { RefCell::new(None) };
+}
+
+/// Imprime `drop: {nome}` quando sai de escopo.
+#[derive(Debug)]
+pub struct Tracer(pub &'static str);
+
+impl Drop for Tracer {
+    fn drop(&mut self) {
+        let name = self.0;
+        let captured = DROP_SINK.with(|sink| match sink.borrow_mut().as_mut() {
+            Some(log) => {
+                log.push(name);
+                true
+            }
+            None => false,
+        });
+        if !captured {
+            println!("drop: {}", name);
+        }
+    }
+}
+
+/// Executa `f` registrando, em ordem, os nomes dos `Tracer` destruídos na
+/// thread atual (em vez de imprimi-los).
+///
+/// Capturas podem ser aninhadas: o que a interna registra também é repassado
+/// para a externa.
+pub fn capture_drops<F: FnOnce()>(f: F) -> Vec<&'static str> {
+    let previous = DROP_SINK.with(|sink| sink.borrow_mut().replace(Vec::new()));
+    let mut guard = RestoreSink(Some(previous));
+    f();
+    guard.restore()
+}
+
+/// Devolve o destino anterior ao sair de [`capture_drops`], inclusive se
+/// `f` entrar em pânico (senão a thread ficaria presa ao registro interno).
+struct RestoreSink(Option<Option<Vec<&'static str>>>);
+
+impl RestoreSink {
+    /// tira o registro desta captura, repõe o destino anterior e repassa a
+    /// ele o que foi registrado
+    fn restore(&mut self) -> Vec<&'static str> {
+        let Some(previous) = self.0.take() else {
+            return Vec::new();
+        };
+        DROP_SINK.with(|sink| {
+            let mut sink = sink.borrow_mut();
+            let log = sink.take().unwrap_or_default();
+            *sink = previous;
+            if let Some(outer) = sink.as_mut() {
+                outer.extend_from_slice(&log);
+            }
+            log
+        })
+    }
+}
+
+impl Drop for RestoreSink {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use memoria_demo::age::{completed_years, parse_year, validate_birth_year, Birth};
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn born_dec_31_is_not_a_year_older_on_jan_1() {
+    assert_eq!(completed_years(date(2000, 12, 31), date(2001, 1, 1)), 0);
+    assert_eq!(completed_years(date(2000, 12, 31), date(2001, 12, 31)), 1);
+}
+
+#[test]
+fn leap_day_birthday_falls_on_feb_28_in_common_years() {
+    let birth = date(2000, 2, 29);
+    assert_eq!(completed_years(birth, date(2001, 2, 27)), 0);
+    assert_eq!(completed_years(birth, date(2001, 2, 28)), 1);
+    assert_eq!(completed_years(birth, date(2004, 2, 28)), 3);
+    assert_eq!(completed_years(birth, date(2004, 2, 29)), 4);
+}
+
+#[test]
+fn parse_accepts_year_or_full_date() {
+    assert_eq!(Birth::parse(" 2001 "), Some(Birth::Year(2001)));
+    assert_eq!(
+        Birth::parse("2001-03-04"),
+        Some(Birth::Date(date(2001, 3, 4)))
+    );
+    assert_eq!(Birth::parse("2001-02-30"), None);
+}
+
+#[test]
+fn validate_birth_year_rejects_future_and_ancient_years() {
+    assert!(validate_birth_year(3000, 2026).is_err());
+    assert_eq!(validate_birth_year(2026, 2026), Ok(2026));
+    assert!(validate_birth_year(1200, 2026).is_err());
+}
+
+#[test]
+fn parse_year_takes_the_first_four_digit_run() {
+    assert_eq!(parse_year("2001"), Some(2001));
+    assert_eq!(parse_year(" 2001 "), Some(2001));
+    assert_eq!(parse_year("ano: 1998"), Some(1998));
+    assert_eq!(parse_year("nasci em 2001"), Some(2001));
+    assert_eq!(parse_year("abcd"), None);
+}
+
+#[test]
+fn parse_accepts_year_inside_text() {
+    assert_eq!(Birth::parse("nasci em 2001"), Some(Birth::Year(2001)));
+}
@@ -0,0 +1,16 @@
+use memoria_demo::alloc_stats::{self, CountingAllocator};
+
+// a biblioteca não instala o alocador; este binário de teste instala
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+use std::hint::black_box;
+
+#[test]
+fn boxing_a_byte_bumps_the_counter() {
+    alloc_stats::enable();
+    let before = alloc_stats::snapshot().allocated;
+    let boxed = black_box(Box::new(0u8));
+    let after = alloc_stats::snapshot().allocated;
+    drop(boxed);
+    assert!(after - before >= 1);
+}
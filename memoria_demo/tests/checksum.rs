@@ -0,0 +1,13 @@
+use memoria_demo::checksum::fnv1a;
+
+#[test]
+fn fnv1a_matches_reference_vectors() {
+    assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+    assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+}
+
+#[test]
+fn fnv1a_changes_when_one_byte_changes() {
+    assert_ne!(fnv1a(b"Ana"), fnv1a(b"ana"));
+}
@@ -0,0 +1,315 @@
+use std::process::{Command, Stdio};
+
+fn demo() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_memoria_demo"))
+}
+
+#[test]
+fn empty_stdin_exits_with_code_4() {
+    let out = demo().stdin(Stdio::null()).output().unwrap();
+    assert_eq!(out.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("entrada encerrada"));
+}
+
+#[test]
+fn invalid_birth_year_flag_exits_with_code_2() {
+    let out = demo()
+        .args(["--name", "Ana", "--birth-year", "abc"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--birth-year"));
+}
+
+#[test]
+fn json_flag_emits_clean_versioned_document() {
+    let out = demo()
+        .args(["--json", "--name", "Ana", "--birth-year", "2001"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(doc["version"], memoria_demo::json::SCHEMA_VERSION);
+    assert!(doc["addresses"]["heap_box_pointee"]
+        .as_str()
+        .unwrap()
+        .starts_with("0x"));
+}
+
+#[test]
+fn lang_en_translates_prompts_and_labels() {
+    let out = demo()
+        .args(["--lang", "en", "--birth-year", "2001"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            child.stdin.take().unwrap().write_all(b"Ana\n")?;
+            child.wait_with_output()
+        })
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Student name: "));
+    assert!(stdout.contains("--- Addresses / Memory hints ---"));
+    assert!(!stdout.contains("Endereços / Pistas"));
+}
+
+#[test]
+fn csv_never_emits_escape_codes_even_with_color_always() {
+    let out = demo()
+        .args([
+            "--csv",
+            "--color=always",
+            "--name",
+            "Ana",
+            "--birth-year",
+            "2001",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with(memoria_demo::csv::HEADER));
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn input_file_with_one_line_exits_with_code_3() {
+    let path = std::env::temp_dir().join("memoria_demo_input_one_line.txt");
+    std::fs::write(&path, "Ana\n").unwrap();
+    let out = demo()
+        .arg("--input")
+        .arg(&path)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("duas linhas"));
+}
+
+#[test]
+fn quiet_prints_only_the_result_without_addresses() {
+    let out = demo()
+        .args(["--quiet", "--name", "Ana", "--birth-year", "2001"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Ana"));
+    assert!(stdout.contains("2001"));
+    assert!(!stdout.contains("0x"));
+}
+
+#[test]
+fn unknown_flag_exits_with_code_2() {
+    let out = demo().arg("--nope").stdin(Stdio::null()).output().unwrap();
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn missing_input_file_exits_with_code_3() {
+    let out = demo()
+        .args(["--input", "/nonexistent/memoria_demo_input.txt"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(3));
+}
+
+#[test]
+fn successful_run_exits_with_code_0() {
+    let out = demo()
+        .args(["--quiet", "--name", "Ana", "--birth-year", "2001"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(0));
+}
+
+#[test]
+fn env_vars_fill_inputs_and_flags_win() {
+    let out = demo()
+        .args(["--quiet", "--name", "Bia"])
+        .env("MEMORIA_NAME", "Ana")
+        .env("MEMORIA_BIRTH_YEAR", "2001")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Bia"));
+    assert!(stdout.contains("2001"));
+    assert!(!stdout.contains("Ana"));
+}
+
+#[test]
+fn malformed_env_birth_year_exits_with_code_2() {
+    let out = demo()
+        .env("MEMORIA_BIRTH_YEAR", "abc")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("MEMORIA_BIRTH_YEAR"));
+}
+
+#[test]
+fn json_timestamp_parses_back_with_chrono() {
+    let out = demo()
+        .args([
+            "--json",
+            "--timestamp",
+            "--name",
+            "Ana",
+            "--birth-year",
+            "2001",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let stamp = doc["timestamp"].as_str().unwrap();
+    assert!(
+        chrono::DateTime::parse_from_rfc3339(stamp).is_ok(),
+        "{}",
+        stamp
+    );
+}
+
+#[test]
+fn unknown_only_name_exits_with_code_2_before_prompting() {
+    let out = demo()
+        .args(["--only", "nao_existe"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("nao_existe") && stderr.contains("válidas"));
+}
+
+#[test]
+fn over_long_multibyte_name_is_truncated_with_a_note() {
+    let name = "é".repeat(300);
+    let out = demo()
+        .args(["--json", "--max-name-len", "10", "--name", &name])
+        .args(["--birth-year", "2001"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(doc["name"], "é".repeat(10));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("caracteres mantidos: 10"));
+}
+
+#[test]
+fn fields_restrict_json_and_unknown_field_lists_valid_ones() {
+    let out = demo()
+        .args(["--json", "--fields", "heap_box,name_buffer"])
+        .args(["--name", "Ana", "--birth-year", "2001"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let keys: Vec<&String> = doc["addresses"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["heap_box", "name_buffer"]);
+
+    let out = demo()
+        .args(["--fields", "heap"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("heap_box_pointee"));
+}
+
+#[test]
+fn invalid_utf8_name_is_replaced_and_the_run_continues() {
+    use std::io::Write;
+    let mut child = demo()
+        .arg("--json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"An\xffa\n2001\n")
+        .unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(doc["name"], "An\u{FFFD}a");
+    assert!(String::from_utf8_lossy(&out.stderr).contains("U+FFFD"));
+}
+
+#[test]
+fn no_addresses_hides_pointers_even_with_color_always() {
+    let out = demo()
+        .args(["--no-addresses", "--color", "always"])
+        .args(["--name", "Ana", "--birth-year", "2000", "--only", "move"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("<rodata:welcome>"));
+    assert!(!stdout.contains("0x"), "endereço vazou: {}", stdout);
+}
+
+#[test]
+fn width_16_pads_report_and_demo_addresses() {
+    let out = demo()
+        .args(["--width", "16", "--color", "never"])
+        .args(["--only", "move,str_kinds,recursion,frame_gap"])
+        .args(["--name", "Ana", "--birth-year", "2000"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let tokens: Vec<&str> = stdout
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| t.starts_with("0x"))
+        .collect();
+    assert!(stdout.contains("--- Move"));
+    assert!(!tokens.is_empty());
+    for token in tokens {
+        assert_eq!(token.len(), 2 + 16, "{}", token);
+    }
+}
+
+#[test]
+fn width_8_reaches_table_demos() {
+    let out = demo()
+        .args(["--width", "8", "--color", "never"])
+        .args(["--only", "str_kinds,recursion,frame_gap"])
+        .args(["--name", "Ana", "--birth-year", "2000"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let demos = &stdout[stdout.find("--- &str vs String").unwrap()..];
+    let tokens: Vec<&str> = demos
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| t.starts_with("0x"))
+        .collect();
+    assert!(!tokens.is_empty());
+    for token in tokens {
+        // sem zeros além dos 8 dígitos pedidos: endereços longos saem inteiros
+        let digits = &token[2..];
+        assert!(digits.len() == 8 || !digits.starts_with('0'), "{}", token);
+    }
+}
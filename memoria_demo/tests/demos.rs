@@ -0,0 +1,156 @@
+use memoria_demo::demos::{self, DemoContext, MemoryDemo};
+use memoria_demo::i18n::Lang;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+fn context() -> DemoContext {
+    DemoContext {
+        threads: true,
+        ..DemoContext::new("Ana", 25)
+    }
+}
+
+#[test]
+fn every_registered_demo_runs_against_a_buffer() {
+    for demo in demos::registry(&context()) {
+        let mut buf = Vec::new();
+        demo.run(&mut buf).unwrap();
+        assert!(!buf.is_empty(), "demo {} não escreveu nada", demo.name());
+    }
+}
+
+#[test]
+fn demo_names_are_unique() {
+    let registry = demos::registry(&context());
+    let names: HashSet<_> = registry.iter().map(|d| d.name().to_string()).collect();
+    assert_eq!(names.len(), registry.len());
+}
+
+#[test]
+fn raw_box_round_trip_keeps_value_and_allocation() {
+    let boxed = Box::new(2025i32);
+    let original = &*boxed as *const i32;
+    let (raw, read, back) = demos::raw_round_trip(boxed);
+    assert_eq!(raw, original);
+    assert_eq!(read, 2025);
+    assert_eq!(*back, 2025);
+}
+
+#[test]
+fn nested_box_levels_are_distinct_allocations() {
+    let ([a, b, c], value) = demos::nested_box_levels(7);
+    assert_eq!(value, 7);
+    assert!(a != b && b != c && a != c);
+}
+
+struct PanickingDemo;
+
+impl MemoryDemo for PanickingDemo {
+    fn name(&self) -> &str {
+        "pânico"
+    }
+
+    fn run(&self, _out: &mut dyn Write) -> io::Result<()> {
+        panic!("demo quebrada de propósito");
+    }
+}
+
+#[test]
+fn panicking_demo_is_reported_and_the_next_one_runs() {
+    let registry = demos::registry(&context());
+    let next = registry[0].as_ref();
+    let mut buf = Vec::new();
+    let failed = demos::run_demos(&mut buf, &[&PanickingDemo, next], false, Lang::Pt).unwrap();
+    assert_eq!(failed, ["pânico"]);
+    let out = String::from_utf8(buf).unwrap();
+    let reported = out.find("demo falhou: pânico").unwrap();
+    // a demo seguinte escreveu depois da falha
+    assert!(out.len() > reported + "demo falhou: pânico\n".len());
+
+    let mut buf = Vec::new();
+    demos::run_demos(&mut buf, &[&PanickingDemo], false, Lang::En).unwrap();
+    assert!(String::from_utf8(buf)
+        .unwrap()
+        .contains("demo failed: pânico"));
+}
+
+#[test]
+fn as_str_points_at_the_string_buffer() {
+    let (owned, view) = demos::as_str_pointers("Ana");
+    assert_eq!(owned, view);
+}
+
+#[test]
+fn move_keeps_capacity_and_clone_fits_the_length() {
+    let [original, moved, cloned] = demos::capacity_on_move("Ana", 64);
+    assert_eq!(moved, original);
+    assert!(original.1 >= 64);
+    assert_eq!(cloned.1, "Ana".len());
+    assert_ne!(cloned.0, original.0);
+}
+
+fn names(selected: &[Box<dyn MemoryDemo>]) -> Vec<&str> {
+    selected.iter().map(|demo| demo.name()).collect()
+}
+
+fn list(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn only_keeps_the_named_demos_in_menu_order() {
+    let registry = demos::registry(&context());
+    let (first, second) = (
+        registry[0].name().to_string(),
+        registry[1].name().to_string(),
+    );
+    let selected = demos::select(registry, &list(&[&second, &first]), &[]).unwrap();
+    assert_eq!(names(&selected), [first.as_str(), second.as_str()]);
+}
+
+#[test]
+fn skip_removes_the_named_demos() {
+    let registry = demos::registry(&context());
+    let total = registry.len();
+    let skipped = registry[0].name().to_string();
+    let selected = demos::select(registry, &[], &list(&[&skipped])).unwrap();
+    assert_eq!(selected.len(), total - 1);
+    assert!(!names(&selected).contains(&skipped.as_str()));
+}
+
+#[test]
+fn unknown_demo_name_lists_the_valid_ones() {
+    let registry = demos::registry(&context());
+    let first = registry[0].name().to_string();
+    let err = demos::select(registry, &list(&["nao_existe"]), &[])
+        .err()
+        .unwrap();
+    assert!(err.contains("nao_existe"));
+    assert!(err.contains(&first));
+    let err = demos::select(demos::registry(&context()), &[], &list(&["xyz"]))
+        .err()
+        .unwrap();
+    assert!(err.contains("xyz"));
+}
+
+#[test]
+fn aslr_verdict_compares_the_three_addresses() {
+    use demos::{aslr_verdict, AslrVerdict};
+    assert_eq!(
+        aslr_verdict(0x1000, Some(0x1000), Some(0x1000)),
+        AslrVerdict::Inactive
+    );
+    assert_eq!(
+        aslr_verdict(0x1000, Some(0x2000), Some(0x3000)),
+        AslrVerdict::Active
+    );
+    assert_eq!(
+        aslr_verdict(0x1000, Some(0x2000), Some(0x2000)),
+        AslrVerdict::Active
+    );
+    assert_eq!(
+        aslr_verdict(0x1000, Some(0x1000), None),
+        AslrVerdict::Unknown
+    );
+    assert_eq!(aslr_verdict(0x1000, None, None), AslrVerdict::Unknown);
+}
@@ -0,0 +1,39 @@
+use memoria_demo::diff::{diff_reports, Segment};
+use memoria_demo::{collect_addresses, json};
+use serde_json::Value;
+
+/// desloca todos os endereços de stack do documento por `delta`
+fn shift_stack(doc: &mut Value, delta: usize) {
+    for label in ["name", "stack_value", "heap_box", "name_chars"] {
+        let slot = &mut doc["addresses"][label];
+        let addr =
+            usize::from_str_radix(slot.as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+        *slot = Value::from(json::hex(addr + delta));
+    }
+}
+
+#[test]
+fn identical_reports_have_no_changes() {
+    let doc = json::report_to_json("Ana", 2001, &collect_addresses("Ana", 2001));
+    let diff = diff_reports(&doc, &doc).unwrap();
+    assert!(diff.entries.iter().all(|e| !e.changed()));
+    assert!(diff.offsets_preserved(Segment::Stack));
+}
+
+#[test]
+fn shifted_stack_changes_addresses_but_keeps_offsets() {
+    let before = json::report_to_json("Ana", 2001, &collect_addresses("Ana", 2001));
+    let mut after = before.clone();
+    shift_stack(&mut after, 0x1000);
+    let diff = diff_reports(&before, &after).unwrap();
+    let name = diff.entries.iter().find(|e| e.label == "name").unwrap();
+    assert!(name.changed());
+    assert_eq!(name.delta(), 0x1000);
+    assert!(diff.offsets_preserved(Segment::Stack));
+
+    // mover só um dos locais quebra a estrutura
+    after["addresses"]["name"] = Value::from(json::hex(name.after + 8));
+    let diff = diff_reports(&before, &after).unwrap();
+    assert!(!diff.offsets_preserved(Segment::Stack));
+    assert!(diff.offsets_preserved(Segment::Heap));
+}
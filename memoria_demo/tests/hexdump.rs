@@ -0,0 +1,12 @@
+use memoria_demo::hexdump::hexdump;
+
+#[test]
+fn dumps_rows_of_sixteen_with_ascii_gutter() {
+    let mut buf = Vec::new();
+    hexdump("Olá, memória do heap!".as_bytes(), 0x1000, &mut buf).unwrap();
+    let expected = "\
+0x0000000000001000  4f 6c c3 a1 2c 20 6d 65  6d c3 b3 72 69 61 20 64  |Ol.., mem..ria d|
+0x0000000000001010  6f 20 68 65 61 70 21                              |o heap!|
+";
+    assert_eq!(String::from_utf8(buf).unwrap(), expected);
+}
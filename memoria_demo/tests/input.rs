@@ -0,0 +1,79 @@
+use memoria_demo::input::{read_line_from, truncate_chars, InputResult, Session};
+use std::fs;
+use std::io::Cursor;
+
+#[test]
+fn line_without_trailing_newline_is_still_a_line() {
+    let mut input = Cursor::new("Ana");
+    assert_eq!(
+        read_line_from(&mut input).unwrap(),
+        InputResult::Line("Ana".to_string())
+    );
+    assert_eq!(read_line_from(&mut input).unwrap(), InputResult::Eof);
+}
+
+#[test]
+fn immediate_eof_is_not_an_empty_line() {
+    assert_eq!(
+        read_line_from(&mut Cursor::new("")).unwrap(),
+        InputResult::Eof
+    );
+    assert_eq!(
+        read_line_from(&mut Cursor::new("\n")).unwrap(),
+        InputResult::Line(String::new())
+    );
+}
+
+#[test]
+fn only_the_trailing_newline_is_removed() {
+    let mut input = Cursor::new("  2001 \r\n");
+    assert_eq!(
+        read_line_from(&mut input).unwrap(),
+        InputResult::Line("  2001 ".to_string())
+    );
+}
+
+#[test]
+fn recorded_session_replays_in_order() {
+    let path = std::env::temp_dir().join(format!("memoria_record_{}.txt", std::process::id()));
+    let file = fs::File::create(&path).unwrap();
+    let mut recording = Session::live().recording_to(Box::new(file));
+    let mut typed = Cursor::new("Ana\n2001-03-04\nt\n");
+    for _ in 0..3 {
+        recording.read_line(&mut typed).unwrap();
+    }
+    drop(recording);
+
+    let text = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(text, "Ana\n2001-03-04\nt\n");
+
+    let mut replay = Session::replaying(&text);
+    let mut nothing = Cursor::new("");
+    for expected in ["Ana", "2001-03-04", "t"] {
+        assert_eq!(
+            replay.read_line(&mut nothing).unwrap(),
+            InputResult::Line(expected.to_string())
+        );
+    }
+    // gravação esgotada é erro, não EOF
+    assert!(replay.read_line(&mut nothing).is_err());
+}
+
+#[test]
+fn truncation_keeps_whole_multibyte_chars() {
+    let long = "ção🦀".repeat(100);
+    let kept = truncate_chars(&long, 5);
+    assert_eq!(kept, "ção🦀ç");
+    assert_eq!(kept.chars().count(), 5);
+    assert_eq!(truncate_chars("Ana", 256), "Ana");
+}
+
+#[test]
+fn invalid_utf8_becomes_replacement_char() {
+    let mut input = Cursor::new(&b"Jo\xe3o\r\n"[..]);
+    assert_eq!(
+        read_line_from(&mut input).unwrap(),
+        InputResult::Line("Jo\u{FFFD}o".to_string())
+    );
+}
@@ -0,0 +1,77 @@
+use std::mem::size_of;
+
+#[test]
+fn slice_reference_is_two_words() {
+    assert_eq!(size_of::<&[i32]>(), 2 * size_of::<usize>());
+}
+
+#[test]
+fn repr_c_example_is_padded_to_twelve_bytes() {
+    use memoria_demo::demos::{PackedExample, ReprCExample};
+    assert_eq!(size_of::<ReprCExample>(), 12);
+    assert_eq!(size_of::<PackedExample>(), 6);
+}
+
+#[test]
+fn zero_sized_types_never_allocate() {
+    assert_eq!(size_of::<()>(), 0);
+    let mut units: Vec<()> = Vec::new();
+    let before = units.as_ptr();
+    for _ in 0..1000 {
+        units.push(());
+    }
+    assert_eq!(units.as_ptr(), before);
+}
+
+#[test]
+fn c_like_enum_is_one_byte() {
+    use memoria_demo::demos::Color;
+    assert_eq!(size_of::<Color>(), 1);
+    assert_eq!(Color::Blue as u8, 4);
+}
+
+#[test]
+fn vec_elements_are_eight_bytes_apart() {
+    let values: Vec<i64> = vec![1, 2, 3, 4];
+    let addrs = memoria_demo::demos::element_addresses(&values);
+    assert_eq!(addrs[0], values.as_ptr() as usize);
+    for pair in addrs.windows(2) {
+        assert_eq!(pair[1] - pair[0], 8);
+    }
+}
+
+#[test]
+fn same_type_shares_vtable_and_other_type_does_not() {
+    use memoria_demo::demos::vtable_of;
+    use std::fmt::Display;
+    let a: Box<dyn Display> = Box::new(1i32);
+    let b: Box<dyn Display> = Box::new(2i32);
+    let c: Box<dyn Display> = Box::new("x");
+    assert_eq!(vtable_of(&*a), vtable_of(&*b));
+    assert_ne!(vtable_of(&*a), vtable_of(&*c));
+}
+
+#[test]
+fn boxed_slice_handle_is_two_words() {
+    assert_eq!(size_of::<Box<[i32]>>(), 2 * size_of::<usize>());
+    assert_eq!(size_of::<Vec<i32>>(), 3 * size_of::<usize>());
+}
+
+#[test]
+fn offset_from_counts_elements_within_one_vec() {
+    use memoria_demo::demos::element_distance;
+    let bytes: Vec<u8> = (0..16).collect();
+    assert_eq!(element_distance(&bytes, 3, 11), 8);
+    assert_eq!(element_distance(&bytes, 11, 3), -8);
+    let words: Vec<u64> = vec![0; 4];
+    assert_eq!(element_distance(&words, 0, 3), 3);
+}
+
+#[test]
+fn option_ref_is_one_word_and_none_is_zero() {
+    use memoria_demo::demos::option_ref_bits;
+    assert_eq!(size_of::<&i32>(), size_of::<Option<&i32>>());
+    assert_eq!(option_ref_bits(None), 0);
+    let x = 7;
+    assert_eq!(option_ref_bits(Some(&x)), &x as *const i32 as usize);
+}
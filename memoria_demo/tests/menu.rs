@@ -0,0 +1,11 @@
+use memoria_demo::menu::{parse_choice, Choice};
+
+#[test]
+fn menu_accepts_numbers_in_range_and_all() {
+    assert_eq!(parse_choice("2\n", 3), Some(Choice::One(1)));
+    assert_eq!(parse_choice("", 3), Some(Choice::All));
+    assert_eq!(parse_choice("Todos", 3), Some(Choice::All));
+    assert_eq!(parse_choice("0", 3), None);
+    assert_eq!(parse_choice("4", 3), None);
+    assert_eq!(parse_choice("abc", 3), None);
+}
@@ -0,0 +1,66 @@
+use memoria_demo::age::Birth;
+use memoria_demo::i18n::Lang;
+use memoria_demo::{ascii_map, collect_addresses, demos, render};
+
+fn text(buf: Vec<u8>) -> String {
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn report_is_written_to_any_writer() {
+    let report = collect_addresses("Ana", 2001);
+    let mut buf = Vec::new();
+    render::print_report(&mut buf, "Ana", Birth::Year(2001), &report, Lang::Pt).unwrap();
+    let out = text(buf);
+    assert!(out.contains("heap_box pointee"));
+    assert!(out.contains("--- Tamanhos / alinhamentos ---"));
+    assert!(out.contains(&render::hex_address(report.heap_box_pointee)));
+}
+
+#[test]
+fn drop_order_demo_writes_drops_in_lifo_order() {
+    let mut buf = Vec::new();
+    demos::demo_drop_order(&mut buf).unwrap();
+    let out = text(buf);
+    let d = out.find("drop: d (stack)").unwrap();
+    let a = out.find("drop: a (stack)").unwrap();
+    assert!(d < a);
+}
+
+#[test]
+fn totals_for_fixed_input() {
+    use std::mem::size_of;
+    let report = collect_addresses("Ana", 2001);
+    let totals = report.totals();
+    // i32 + Box<i32> + String + Vec<char>
+    let words = size_of::<usize>();
+    assert_eq!(totals.stack, 4 + words + 3 * words + 3 * words);
+    // "Ana" é copiado com capacidade exata; o Vec<char> guarda ao menos 3 chars
+    assert_eq!(report.name_capacity, 3);
+    assert!(report.name_chars_capacity >= 3);
+    assert_eq!(totals.heap, 3 + 4 + report.name_chars_capacity * 4);
+}
+
+#[test]
+fn ascii_map_puts_higher_addresses_first() {
+    let report = collect_addresses("Ana", 2001);
+    let mut buf = Vec::new();
+    ascii_map::write_ascii_map(&mut buf, &report).unwrap();
+    let out = text(buf);
+    let row = |label: &str| out.find(&format!("| {:<18}", label)).unwrap();
+    let (high, low) = if report.stack_value > report.welcome {
+        ("stack_value", "welcome")
+    } else {
+        ("welcome", "stack_value")
+    };
+    assert!(row(high) < row(low));
+    assert_eq!(ascii_map::gap_rows(0), 0);
+    assert_eq!(ascii_map::gap_rows(usize::MAX), ascii_map::MAX_GAP_ROWS);
+}
+
+#[test]
+fn section_header_is_plain_without_colors() {
+    let mut buf = Vec::new();
+    render::section(&mut buf, "Ordem de drop (LIFO)").unwrap();
+    assert_eq!(text(buf), "\n--- Ordem de drop (LIFO) ---\n");
+}
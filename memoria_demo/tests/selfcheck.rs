@@ -0,0 +1,10 @@
+use memoria_demo::collect_addresses;
+use memoria_demo::selfcheck::{run_checks, write_self_check};
+
+#[test]
+fn every_check_passes_on_this_platform() {
+    let checks = run_checks(&collect_addresses("Ana", 2001));
+    let failed: Vec<_> = checks.iter().filter(|c| !c.passed).collect();
+    assert!(failed.is_empty(), "{:?}", failed);
+    assert!(write_self_check(&mut Vec::new(), &checks).unwrap());
+}
@@ -0,0 +1,22 @@
+use memoria_demo::stack::{self, StackGrowth};
+
+#[test]
+fn growth_direction_matches_target_arch() {
+    // nas arquiteturas comuns (x86, x86_64, arm, aarch64, riscv) a stack cresce para baixo
+    let common = cfg!(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    ));
+    if common {
+        assert_eq!(stack::measure_growth(), StackGrowth::Down);
+    }
+}
+
+#[test]
+fn growth_between_compares_addresses() {
+    assert_eq!(stack::growth_between(0x2000, 0x1000), StackGrowth::Down);
+    assert_eq!(stack::growth_between(0x1000, 0x2000), StackGrowth::Up);
+}
@@ -0,0 +1,16 @@
+use memoria_demo::collect_addresses;
+use memoria_demo::symbols::SymbolTable;
+
+#[test]
+fn report_addresses_get_stable_symbolic_names() {
+    let report = collect_addresses("Ana", 2001);
+    let mut table = SymbolTable::for_report(&report);
+    let line = format!(
+        "{:#x} -> {:#x}; de novo {:#x}; byte 0x41",
+        report.name, report.name_buffer, report.name
+    );
+    assert_eq!(
+        table.symbolize_line(&line),
+        "<stack:name> -> <heap:name_buffer>; de novo <stack:name>; byte 0x41"
+    );
+}
@@ -0,0 +1,55 @@
+use memoria_demo::demos;
+use memoria_demo::tracer::{capture_drops, Tracer};
+
+#[test]
+fn drop_order_demo_is_lifo() {
+    let order = capture_drops(|| demos::demo_drop_order(&mut Vec::new()).unwrap());
+    assert_eq!(
+        order,
+        ["d (stack)", "c (Box, heap)", "b (stack)", "a (stack)"]
+    );
+}
+
+#[test]
+fn capture_only_sees_drops_inside_the_closure() {
+    let _outer = Tracer("fora");
+    let order = capture_drops(|| {
+        let _x = Tracer("x");
+    });
+    assert_eq!(order, ["x"]);
+}
+
+#[test]
+fn nested_drop_drops_every_element_before_the_vec() {
+    let drops = capture_drops(|| demos::demo_nested_drop(&mut Vec::new()).unwrap());
+    let elements = drops.iter().filter(|d| d.starts_with("elemento")).count();
+    assert_eq!(elements, demos::NESTED_DROP_LEN);
+    assert!(drops.last().unwrap().starts_with("Vec concluído"));
+}
+
+#[test]
+fn boxed_tracer_drops_in_lifo_order_with_the_stack_ones() {
+    assert_eq!(
+        demos::box_drop_order(),
+        ["c (stack)", "b (Box, heap)", "a (stack)"]
+    );
+}
+
+#[test]
+fn panic_inside_capture_restores_the_outer_sink() {
+    use std::panic::{self, AssertUnwindSafe};
+    let outer = capture_drops(|| {
+        drop(Tracer("antes"));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            capture_drops(|| {
+                let _a = Tracer("a");
+                panic!("demo em pânico");
+            })
+        }));
+        assert!(result.is_err());
+        drop(Tracer("depois"));
+    });
+    assert_eq!(outer, ["antes", "a", "depois"]);
+    // e uma captura nova depois do pânico continua registrando
+    assert_eq!(capture_drops(|| drop(Tracer("c"))), ["c"]);
+}
@@ -0,0 +1,25 @@
+use memoria_demo::render::{self, hex_address};
+use memoria_demo::{collect_addresses, csv};
+
+// um único teste: a largura é global e os testes de um arquivo rodam em paralelo
+#[test]
+fn addresses_are_padded_to_the_requested_width() {
+    render::set_address_width(Some(8));
+    assert_eq!(hex_address(0x1234), "0x00001234");
+    // mais longo que a largura: sai inteiro
+    assert_eq!(hex_address(0x1_2345_6789), "0x123456789");
+
+    render::set_address_width(Some(16));
+    assert_eq!(hex_address(0x1234), "0x0000000000001234");
+    let report = collect_addresses("Ana", 2001);
+    let mut buf = Vec::new();
+    csv::write_report_csv(&mut buf, &report).unwrap();
+    let out = String::from_utf8(buf).unwrap();
+    for line in out.lines().skip(1) {
+        let hex = line.split(',').nth(2).unwrap();
+        assert_eq!(hex.len(), 2 + 16, "{}", line);
+    }
+
+    render::set_address_width(None);
+    assert_eq!(render::address_width(), std::mem::size_of::<usize>() * 2);
+}